@@ -6,6 +6,9 @@ fn main() {
     bindgen::Builder::default()
         .header("wrapper.h")
         .generate_comments(false)
+        // packed structs containing aligned frames cannot be represented
+        .opaque_type("cgw_frame_mod")
+        .opaque_type("cgw_fdframe_mod")
         .generate()
         .unwrap()
         .write_to_file(out_dir.join("bindings.rs"))
@@ -1,4 +1,4 @@
-use socketcan_alt::{Cmsg, Socket, Timestamping};
+use socketcan_alt::{Socket, Timestamping};
 use std::ffi::CString;
 use std::io::Result;
 use structopt::StructOpt;
@@ -15,21 +15,16 @@ fn main() -> Result<()> {
     socket.set_timestamping(Timestamping::RX_SOFTWARE | Timestamping::SOFTWARE)?;
     socket.set_fd_frames(true)?;
 
-    let mut cmsg_buf = vec![0; Cmsg::space()];
     loop {
-        let (frame, cmsgs) = socket.recv_msg(&mut cmsg_buf)?;
-        let timestamp = cmsgs.into_iter().flatten().find_map(|cmsg| match cmsg {
-            Cmsg::Timestamping(ts) => Some(ts[0]),
-            _ => None,
-        });
-        if let Some(timestamp) = timestamp {
+        let received = socket.recv_frame()?;
+        if let Some([timestamp, ..]) = received.timestamps {
             println!(
                 "{:.9} {:?}",
                 timestamp.tv_sec as f64 + timestamp.tv_nsec as f64 / 1_000_000_000.,
-                frame
+                received.frame
             );
         } else {
-            println!("{:?}", frame);
+            println!("{:?}", received.frame);
         }
     }
 }
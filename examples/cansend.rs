@@ -25,13 +25,14 @@ fn main() -> io::Result<()> {
     } else {
         Id::Extended(opt.id)
     };
-    let frame = match opt.brs {
-        None => Frame::Data(DataFrame::new(id, &opt.data)),
-        Some(brs) => {
-            socket.set_fd_frames(true)?;
-            Frame::FdData(FdDataFrame::new(id, brs, false, &opt.data))
-        }
-    };
+    let frame = FrameBuilder::new(id)
+        .data(&opt.data)
+        .fd(opt.brs.is_some())
+        .brs(opt.brs.unwrap_or_default())
+        .build();
+    if let Frame::FdData(_) = frame {
+        socket.set_fd_frames(true)?;
+    }
     socket.send(&frame)?;
 
     Ok(())
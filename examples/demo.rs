@@ -34,11 +34,10 @@ fn main() -> Result<()> {
         } else {
             Id::Standard(42)
         };
-        let frame = if count % 5 == 0 {
-            Frame::FdData(FdDataFrame::new(id, false, false, &count.to_be_bytes()))
-        } else {
-            Frame::Data(DataFrame::new(id, &count.to_be_bytes()))
-        };
+        let frame = FrameBuilder::new(id)
+            .data(&count.to_be_bytes())
+            .fd(count % 5 == 0)
+            .build();
         socket.send(&frame)?;
         count += 1;
         thread::sleep(Duration::new(1, 0));
@@ -1,39 +1,95 @@
-use crate::{CmsgIter, Frame, Timestamping};
+mod bcm;
+mod interface_watcher;
+mod isotp;
+mod j1939;
+mod link_monitor;
+mod reconnecting_socket;
+mod requester;
+mod split;
+
+use crate::{CmsgIter, Error, Frame, ReceivedFrame, Result, Timestamping};
+pub use bcm::BcmSocket;
+use futures_core::Stream;
+use futures_sink::Sink;
+pub use interface_watcher::InterfaceWatcher;
+pub use isotp::IsoTpSocket;
+pub use j1939::J1939Socket;
+pub use link_monitor::LinkMonitor;
+pub use reconnecting_socket::ReconnectingSocket;
+pub use requester::Requester;
+pub use split::{OwnedReadHalf, OwnedWriteHalf, ReadHalf, ReuniteError, WriteHalf};
 use std::ffi::CStr;
-use std::io::{ErrorKind, Result};
+use std::io::{self, ErrorKind};
 use std::os::unix::io::{AsRawFd, RawFd};
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
 use tokio::io::unix::AsyncFd;
 
-pub struct Socket(AsyncFd<crate::Socket>);
+/// An asynchronous [`Socket`](crate::Socket).
+///
+/// It is also a [`Stream`] of received frames and a [`Sink`] of frames to send,
+/// so frames can be forwarded from one interface to another.
+/// [`received_frames`](Self::received_frames) makes a stream of frames with their metadata.
+pub struct Socket {
+    inner: AsyncFd<crate::Socket>,
+    // the frame accepted by Sink::start_send but not sent yet
+    sending: Option<Frame>,
+}
 
 impl Socket {
     pub fn bind<I>(ifname: I) -> Result<Self>
     where
         I: AsRef<CStr>,
     {
-        let socket = crate::Socket::bind(ifname)?;
+        Self::new(crate::Socket::bind(ifname)?)
+    }
+
+    fn new(socket: crate::Socket) -> Result<Self> {
         socket.set_nonblocking(true)?;
-        Ok(Self(AsyncFd::new(socket)?))
+        Ok(Self {
+            inner: AsyncFd::new(socket)?,
+            sending: None,
+        })
+    }
+
+    pub fn mtu(&self) -> usize {
+        self.inner.get_ref().mtu()
+    }
+
+    pub fn refresh_mtu(&self) -> Result<usize> {
+        self.inner.get_ref().refresh_mtu()
+    }
+
+    pub fn supports_fd(&self) -> bool {
+        self.inner.get_ref().supports_fd()
+    }
+
+    pub fn supports_xl(&self) -> bool {
+        self.inner.get_ref().supports_xl()
     }
 
     pub fn set_timestamping(&self, timestamping: Timestamping) -> Result<()> {
-        self.0.get_ref().set_timestamping(timestamping)
+        self.inner.get_ref().set_timestamping(timestamping)
     }
 
     pub fn set_recv_own_msgs(&self, enable: bool) -> Result<()> {
-        self.0.get_ref().set_recv_own_msgs(enable)
+        self.inner.get_ref().set_recv_own_msgs(enable)
     }
 
     pub fn set_fd_frames(&self, enable: bool) -> Result<()> {
-        self.0.get_ref().set_fd_frames(enable)
+        self.inner.get_ref().set_fd_frames(enable)
+    }
+
+    pub fn set_fd_frames_if_supported(&self) -> Result<bool> {
+        self.inner.get_ref().set_fd_frames_if_supported()
+    }
+
+    pub fn set_rxq_ovfl(&self, enable: bool) -> Result<()> {
+        self.inner.get_ref().set_rxq_ovfl(enable)
     }
 
     pub async fn recv(&self) -> Result<Frame> {
-        loop {
-            if let Ok(v) = self.0.readable().await?.try_io(|s| s.get_ref().recv()) {
-                break v;
-            }
-        }
+        recv(&self.inner).await
     }
 
     #[allow(clippy::needless_lifetimes)]
@@ -41,31 +97,181 @@ impl Socket {
         &self,
         cmsg_buf: &'a mut [u8],
     ) -> Result<(Frame, Option<CmsgIter<'a>>)> {
-        let mut cmsg_buf = Some(cmsg_buf);
-        loop {
-            let mut guard = self.0.readable().await?;
-            match self.0.get_ref()._recv_msg(cmsg_buf.take().unwrap()) {
-                Err((e, b)) if e.kind() == ErrorKind::WouldBlock => {
-                    cmsg_buf = Some(b);
-                    guard.clear_ready();
-                }
-                r => break r.map_err(|(e, _)| e),
+        recv_msg(&self.inner, cmsg_buf).await
+    }
+
+    pub async fn recv_frame(&self) -> Result<ReceivedFrame> {
+        recv_frame(&self.inner).await
+    }
+
+    pub async fn send(&self, frame: &Frame) -> Result<()> {
+        send(&self.inner, frame).await
+    }
+
+    /// Returns a stream of frames with their metadata, like [`recv_frame`](Self::recv_frame).
+    pub fn received_frames(&self) -> ReceivedFrames<'_> {
+        ReceivedFrames(&self.inner)
+    }
+
+    /// Splits the socket into a receiving half and a sending half,
+    /// which can be used concurrently without contention.
+    pub fn split(&mut self) -> (ReadHalf<'_>, WriteHalf<'_>) {
+        split::split(self)
+    }
+
+    /// Splits the socket into owned halves, which can be moved to different tasks.
+    /// [`OwnedReadHalf::reunite`] restores the socket.
+    pub fn into_split(self) -> (OwnedReadHalf, OwnedWriteHalf) {
+        split::into_split(self)
+    }
+}
+
+// the operations on the socket shared with the halves made by Socket::split,
+// which borrow the socket but not the frame accepted by Sink::start_send
+
+async fn recv(inner: &AsyncFd<crate::Socket>) -> Result<Frame> {
+    loop {
+        if let Ok(v) = inner
+            .readable()
+            .await?
+            .try_io(|s| s.get_ref().recv().map_err(io::Error::from))
+        {
+            break v.map_err(Error::from);
+        }
+    }
+}
+
+async fn recv_msg<'a>(
+    inner: &AsyncFd<crate::Socket>,
+    cmsg_buf: &'a mut [u8],
+) -> Result<(Frame, Option<CmsgIter<'a>>)> {
+    let mut cmsg_buf = Some(cmsg_buf);
+    loop {
+        let mut guard = inner.readable().await?;
+        match inner.get_ref()._recv_msg(cmsg_buf.take().unwrap()) {
+            Err((e, b)) if e.kind() == ErrorKind::WouldBlock => {
+                cmsg_buf = Some(b);
+                guard.clear_ready();
             }
+            r => break r.map_err(|(e, _)| e),
         }
     }
+}
 
-    pub async fn send(&self, frame: &Frame) -> Result<()> {
+async fn recv_frame(inner: &AsyncFd<crate::Socket>) -> Result<ReceivedFrame> {
+    loop {
+        if let Ok(v) = inner
+            .readable()
+            .await?
+            .try_io(|s| s.get_ref().recv_frame().map_err(io::Error::from))
+        {
+            break v.map_err(Error::from);
+        }
+    }
+}
+
+async fn send(inner: &AsyncFd<crate::Socket>, frame: &Frame) -> Result<()> {
+    loop {
+        if let Ok(v) = inner
+            .writable()
+            .await?
+            .try_io(|s| s.get_ref().send(frame).map_err(io::Error::from))
+        {
+            break v.map_err(Error::from);
+        }
+    }
+}
+
+fn poll_recv(inner: &AsyncFd<crate::Socket>, cx: &mut Context<'_>) -> Poll<Result<Frame>> {
+    loop {
+        let mut guard = ready!(inner.poll_read_ready(cx))?;
+        if let Ok(v) = guard.try_io(|s| s.get_ref().recv().map_err(io::Error::from)) {
+            return Poll::Ready(v.map_err(Error::from));
+        }
+    }
+}
+
+fn poll_recv_frame(
+    inner: &AsyncFd<crate::Socket>,
+    cx: &mut Context<'_>,
+) -> Poll<Result<ReceivedFrame>> {
+    loop {
+        let mut guard = ready!(inner.poll_read_ready(cx))?;
+        if let Ok(v) = guard.try_io(|s| s.get_ref().recv_frame().map_err(io::Error::from)) {
+            return Poll::Ready(v.map_err(Error::from));
+        }
+    }
+}
+
+// sends the frame accepted by Sink::start_send if any
+fn poll_send_pending(
+    inner: &AsyncFd<crate::Socket>,
+    sending: &mut Option<Frame>,
+    cx: &mut Context<'_>,
+) -> Poll<Result<()>> {
+    if let Some(frame) = sending {
         loop {
-            if let Ok(v) = self.0.writable().await?.try_io(|s| s.get_ref().send(frame)) {
-                break v;
+            let mut guard = ready!(inner.poll_write_ready(cx))?;
+            if let Ok(v) = guard.try_io(|s| s.get_ref().send(frame).map_err(io::Error::from)) {
+                *sending = None;
+                return Poll::Ready(v.map_err(Error::from));
             }
         }
     }
+    Poll::Ready(Ok(()))
+}
+
+/// Frames are yielded with the [`Error`] of this crate rather than [`io::Error`],
+/// which it converts into, matching the [`Sink`] so that the stream of one socket
+/// can be forwarded to another one.
+impl Stream for Socket {
+    type Item = Result<Frame>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        poll_recv(&self.inner, cx).map(Some)
+    }
+}
+
+/// Frames are sent one at a time. A frame which fails to be sent is dropped
+/// and the error is returned from the next call of `poll_ready`, `poll_flush` or `poll_close`.
+impl Sink<Frame> for Socket {
+    type Error = Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let this = self.get_mut();
+        poll_send_pending(&this.inner, &mut this.sending, cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, frame: Frame) -> Result<()> {
+        self.get_mut().sending = Some(frame);
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let this = self.get_mut();
+        poll_send_pending(&this.inner, &mut this.sending, cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let this = self.get_mut();
+        poll_send_pending(&this.inner, &mut this.sending, cx)
+    }
 }
 
 impl AsRawFd for Socket {
     fn as_raw_fd(&self) -> RawFd {
-        self.0.as_raw_fd()
+        self.inner.as_raw_fd()
+    }
+}
+
+/// A [`Stream`] of frames with their metadata, made by [`Socket::received_frames`].
+pub struct ReceivedFrames<'a>(&'a AsyncFd<crate::Socket>);
+
+impl Stream for ReceivedFrames<'_> {
+    type Item = Result<ReceivedFrame>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        poll_recv_frame(self.0, cx).map(Some)
     }
 }
 
@@ -0,0 +1,80 @@
+use crate::{BcmMessage, Error, Id, Result, RxSetup, TxSetup};
+use std::ffi::CStr;
+use std::io;
+use std::os::unix::io::{AsRawFd, RawFd};
+use tokio::io::unix::AsyncFd;
+
+pub struct BcmSocket(AsyncFd<crate::BcmSocket>);
+
+impl BcmSocket {
+    pub fn connect<I>(ifname: I) -> Result<Self>
+    where
+        I: AsRef<CStr>,
+    {
+        let socket = crate::BcmSocket::connect(ifname)?;
+        socket.set_nonblocking(true)?;
+        Ok(Self(AsyncFd::new(socket)?))
+    }
+
+    pub async fn tx_setup(&self, setup: &TxSetup<'_>) -> Result<()> {
+        self.write(|s| s.tx_setup(setup)).await
+    }
+
+    pub async fn tx_delete(&self, id: Id, fd: bool) -> Result<()> {
+        self.write(|s| s.tx_delete(id, fd)).await
+    }
+
+    pub async fn tx_read(&self, id: Id, fd: bool) -> Result<()> {
+        self.write(|s| s.tx_read(id, fd)).await
+    }
+
+    pub async fn rx_setup(&self, setup: &RxSetup<'_>) -> Result<()> {
+        self.write(|s| s.rx_setup(setup)).await
+    }
+
+    pub async fn rx_delete(&self, id: Id, fd: bool) -> Result<()> {
+        self.write(|s| s.rx_delete(id, fd)).await
+    }
+
+    pub async fn rx_read(&self, id: Id, fd: bool) -> Result<()> {
+        self.write(|s| s.rx_read(id, fd)).await
+    }
+
+    pub async fn recv(&self) -> Result<BcmMessage> {
+        loop {
+            if let Ok(v) = self
+                .0
+                .readable()
+                .await?
+                .try_io(|s| s.get_ref().recv().map_err(io::Error::from))
+            {
+                break v.map_err(Error::from);
+            }
+        }
+    }
+
+    async fn write<F>(&self, f: F) -> Result<()>
+    where
+        F: Fn(&crate::BcmSocket) -> Result<()>,
+    {
+        loop {
+            if let Ok(v) = self
+                .0
+                .writable()
+                .await?
+                .try_io(|s| f(s.get_ref()).map_err(io::Error::from))
+            {
+                break v.map_err(Error::from);
+            }
+        }
+    }
+}
+
+impl AsRawFd for BcmSocket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.as_raw_fd()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,52 @@
+use super::BcmSocket;
+use crate::socket::tests::{ifname, LOCK};
+use crate::{BcmMessage, DataFrame, Frame, Id, RxSetup, TxSetup};
+use std::ffi::CString;
+use std::time::Duration;
+use tokio::time::timeout;
+
+macro_rules! lock {
+    (shared) => {
+        let _lock = LOCK.read();
+    };
+    (exclusive) => {
+        let _lock = LOCK.write();
+    };
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_connect() {
+    BcmSocket::connect(ifname()).unwrap();
+}
+
+#[tokio::test]
+async fn test_connect_no_device() {
+    let ifname = CString::new("NO DEVICE").unwrap();
+    assert!(BcmSocket::connect(ifname).is_err());
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_tx_rx() {
+    lock!(exclusive);
+    let tx = BcmSocket::connect(ifname()).unwrap();
+    let rx = BcmSocket::connect(ifname()).unwrap();
+
+    rx.rx_setup(&RxSetup::new(Id::Standard(42))).await.unwrap();
+    let frames = [Frame::Data(DataFrame::new(Id::Standard(42), &[0, 1, 2, 3]))];
+    tx.tx_setup(&TxSetup::new(Id::Standard(42), &frames).count(1, Duration::from_millis(10)))
+        .await
+        .unwrap();
+    let message = timeout(Duration::from_millis(100), rx.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        message,
+        BcmMessage::RxChanged {
+            id: Id::Standard(42),
+            frame: frames[0],
+        }
+    );
+}
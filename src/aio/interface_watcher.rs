@@ -0,0 +1,60 @@
+use crate::{Error, InterfaceEvent, Result};
+use futures_core::Stream;
+use std::io;
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+use tokio::io::unix::AsyncFd;
+
+/// An asynchronous [`InterfaceWatcher`](crate::InterfaceWatcher), which is also a [`Stream`] of events.
+pub struct InterfaceWatcher(AsyncFd<crate::InterfaceWatcher>);
+
+impl InterfaceWatcher {
+    pub fn new() -> Result<Self> {
+        let watcher = crate::InterfaceWatcher::new()?;
+        watcher.set_nonblocking(true)?;
+        Ok(Self(AsyncFd::new(watcher)?))
+    }
+
+    pub async fn recv(&mut self) -> Result<InterfaceEvent> {
+        if let Some(event) = self.0.get_mut().pop_pending() {
+            return Ok(event);
+        }
+        loop {
+            if let Ok(v) = self
+                .0
+                .readable_mut()
+                .await?
+                .try_io(|s| s.get_mut().recv().map_err(io::Error::from))
+            {
+                break v.map_err(Error::from);
+            }
+        }
+    }
+}
+
+impl Stream for InterfaceWatcher {
+    type Item = Result<InterfaceEvent>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if let Some(event) = this.0.get_mut().pop_pending() {
+            return Poll::Ready(Some(Ok(event)));
+        }
+        loop {
+            let mut guard = ready!(this.0.poll_read_ready_mut(cx))?;
+            if let Ok(v) = guard.try_io(|s| s.get_mut().recv().map_err(io::Error::from)) {
+                return Poll::Ready(Some(v.map_err(Error::from)));
+            }
+        }
+    }
+}
+
+impl AsRawFd for InterfaceWatcher {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.as_raw_fd()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,48 @@
+use super::InterfaceWatcher;
+use crate::socket::tests::{ifname, LOCK};
+use crate::{CanLink, InterfaceEvent};
+use futures_core::Stream;
+use std::future::poll_fn;
+use std::pin::Pin;
+use std::time::Duration;
+use tokio::time::timeout;
+
+macro_rules! lock {
+    (shared) => {
+        let _lock = LOCK.read();
+    };
+    (exclusive) => {
+        let _lock = LOCK.write();
+    };
+}
+
+#[tokio::test]
+async fn test_recv_nothing() {
+    let mut watcher = InterfaceWatcher::new().unwrap();
+    assert!(timeout(Duration::from_millis(100), watcher.recv())
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_stream() {
+    lock!(exclusive);
+    let link = CanLink::new(ifname()).unwrap();
+    let mut watcher = InterfaceWatcher::new().unwrap();
+    link.set_up(false).unwrap();
+    link.set_up(true).unwrap();
+
+    let event = timeout(
+        Duration::from_millis(100),
+        poll_fn(|cx| Pin::new(&mut watcher).poll_next(cx)),
+    )
+    .await
+    .unwrap()
+    .unwrap()
+    .unwrap();
+    match event {
+        InterfaceEvent::Down(interface) => assert_eq!(interface.index, link.ifindex()),
+        event => panic!("{:?}", event),
+    }
+}
@@ -0,0 +1,53 @@
+use crate::{Error, Id, IsoTpOptions, Result};
+use std::ffi::CStr;
+use std::io;
+use std::os::unix::io::{AsRawFd, RawFd};
+use tokio::io::unix::AsyncFd;
+
+pub struct IsoTpSocket(AsyncFd<crate::IsoTpSocket>);
+
+impl IsoTpSocket {
+    pub fn bind<I>(ifname: I, rx_id: Id, tx_id: Id, options: &IsoTpOptions) -> Result<Self>
+    where
+        I: AsRef<CStr>,
+    {
+        let socket = crate::IsoTpSocket::bind(ifname, rx_id, tx_id, options)?;
+        socket.set_nonblocking(true)?;
+        Ok(Self(AsyncFd::new(socket)?))
+    }
+
+    pub async fn send(&self, pdu: &[u8]) -> Result<()> {
+        loop {
+            if let Ok(v) = self
+                .0
+                .writable()
+                .await?
+                .try_io(|s| s.get_ref().send(pdu).map_err(io::Error::from))
+            {
+                break v.map_err(Error::from);
+            }
+        }
+    }
+
+    pub async fn recv(&self, buf: &mut [u8]) -> Result<usize> {
+        loop {
+            if let Ok(v) = self
+                .0
+                .readable()
+                .await?
+                .try_io(|s| s.get_ref().recv(buf).map_err(io::Error::from))
+            {
+                break v.map_err(Error::from);
+            }
+        }
+    }
+}
+
+impl AsRawFd for IsoTpSocket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.as_raw_fd()
+    }
+}
+
+#[cfg(test)]
+mod tests;
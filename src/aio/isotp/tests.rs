@@ -0,0 +1,48 @@
+use super::IsoTpSocket;
+use crate::socket::tests::{ifname, LOCK};
+use crate::{Id, IsoTpOptions};
+use std::ffi::CString;
+use std::time::Duration;
+use tokio::time::timeout;
+
+macro_rules! lock {
+    (shared) => {
+        let _lock = LOCK.read();
+    };
+    (exclusive) => {
+        let _lock = LOCK.write();
+    };
+}
+
+#[tokio::test]
+async fn test_bind_no_device() {
+    let ifname = CString::new("NO DEVICE").unwrap();
+    assert!(IsoTpSocket::bind(
+        ifname,
+        Id::Standard(0x7e8),
+        Id::Standard(0x7e0),
+        &IsoTpOptions::new()
+    )
+    .is_err());
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_transfer() {
+    lock!(shared);
+    let options = IsoTpOptions::new();
+    let a =
+        IsoTpSocket::bind(ifname(), Id::Standard(0x7e8), Id::Standard(0x7e0), &options).unwrap();
+    let b =
+        IsoTpSocket::bind(ifname(), Id::Standard(0x7e0), Id::Standard(0x7e8), &options).unwrap();
+
+    let pdu = (0..1000).map(|i| i as u8).collect::<Vec<_>>();
+    let mut buf = vec![0; 4095];
+    let (sent, received) = tokio::join!(
+        a.send(&pdu),
+        timeout(Duration::from_millis(500), b.recv(&mut buf))
+    );
+    sent.unwrap();
+    let size = received.unwrap().unwrap();
+    assert_eq!(&buf[..size], pdu);
+}
@@ -0,0 +1,120 @@
+use crate::{Error, J1939Address, J1939Event, J1939Filter, J1939Received, Result};
+use std::ffi::CStr;
+use std::io;
+use std::os::unix::io::{AsRawFd, RawFd};
+use tokio::io::unix::AsyncFd;
+use tokio::io::Interest;
+
+pub struct J1939Socket(AsyncFd<crate::J1939Socket>);
+
+impl J1939Socket {
+    pub fn bind<I>(ifname: I, address: J1939Address) -> Result<Self>
+    where
+        I: AsRef<CStr>,
+    {
+        let socket = crate::J1939Socket::bind(ifname, address)?;
+        socket.set_nonblocking(true)?;
+        Ok(Self(AsyncFd::with_interest(
+            socket,
+            Interest::READABLE | Interest::WRITABLE | Interest::ERROR,
+        )?))
+    }
+
+    pub fn connect(&self, address: J1939Address) -> Result<()> {
+        self.0.get_ref().connect(address)
+    }
+
+    pub fn set_broadcast(&self, enable: bool) -> Result<()> {
+        self.0.get_ref().set_broadcast(enable)
+    }
+
+    pub fn set_promisc(&self, enable: bool) -> Result<()> {
+        self.0.get_ref().set_promisc(enable)
+    }
+
+    pub fn set_send_priority(&self, priority: u8) -> Result<()> {
+        self.0.get_ref().set_send_priority(priority)
+    }
+
+    pub fn set_filters(&self, filters: &[J1939Filter]) -> Result<()> {
+        self.0.get_ref().set_filters(filters)
+    }
+
+    pub fn set_errqueue(&self, enable: bool) -> Result<()> {
+        self.0.get_ref().set_errqueue(enable)
+    }
+
+    pub async fn send(&self, data: &[u8]) -> Result<()> {
+        loop {
+            if let Ok(v) = self
+                .0
+                .writable()
+                .await?
+                .try_io(|s| s.get_ref().send(data).map_err(io::Error::from))
+            {
+                break v.map_err(Error::from);
+            }
+        }
+    }
+
+    pub async fn send_to(&self, data: &[u8], address: J1939Address) -> Result<()> {
+        loop {
+            if let Ok(v) = self
+                .0
+                .writable()
+                .await?
+                .try_io(|s| s.get_ref().send_to(data, address).map_err(io::Error::from))
+            {
+                break v.map_err(Error::from);
+            }
+        }
+    }
+
+    pub async fn recv(&self, buf: &mut [u8]) -> Result<usize> {
+        loop {
+            if let Ok(v) = self
+                .0
+                .readable()
+                .await?
+                .try_io(|s| s.get_ref().recv(buf).map_err(io::Error::from))
+            {
+                break v.map_err(Error::from);
+            }
+        }
+    }
+
+    pub async fn recv_from(&self, buf: &mut [u8]) -> Result<(usize, J1939Received)> {
+        loop {
+            if let Ok(v) = self
+                .0
+                .readable()
+                .await?
+                .try_io(|s| s.get_ref().recv_from(buf).map_err(io::Error::from))
+            {
+                break v.map_err(Error::from);
+            }
+        }
+    }
+
+    pub async fn recv_event(&self) -> Result<J1939Event> {
+        loop {
+            if let Ok(v) = self
+                .0
+                .ready(Interest::ERROR)
+                .await?
+                .try_io(|s| s.get_ref().recv_event().map_err(io::Error::from))
+            {
+                break v.map_err(Error::from);
+            }
+        }
+    }
+}
+
+impl AsRawFd for J1939Socket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.as_raw_fd()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,67 @@
+use super::J1939Socket;
+use crate::socket::tests::{ifname, LOCK};
+use crate::J1939Address;
+use std::ffi::CString;
+use std::time::Duration;
+use tokio::time::timeout;
+
+macro_rules! lock {
+    (shared) => {
+        let _lock = LOCK.read();
+    };
+    (exclusive) => {
+        let _lock = LOCK.write();
+    };
+}
+
+#[tokio::test]
+async fn test_bind_no_device() {
+    let ifname = CString::new("NO DEVICE").unwrap();
+    assert!(J1939Socket::bind(ifname, J1939Address::default()).is_err());
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_send_to() {
+    lock!(exclusive);
+    let a = J1939Socket::bind(
+        ifname(),
+        J1939Address {
+            addr: Some(0x20),
+            ..J1939Address::default()
+        },
+    )
+    .unwrap();
+    let b = J1939Socket::bind(
+        ifname(),
+        J1939Address {
+            addr: Some(0x30),
+            ..J1939Address::default()
+        },
+    )
+    .unwrap();
+
+    a.set_errqueue(true).unwrap();
+    let data = (0..100).collect::<Vec<u8>>();
+    a.send_to(
+        &data,
+        J1939Address {
+            pgn: Some(0x12300),
+            addr: Some(0x30),
+            ..J1939Address::default()
+        },
+    )
+    .await
+    .unwrap();
+    let mut buf = [0; 128];
+    let (size, received) = timeout(Duration::from_millis(500), b.recv_from(&mut buf))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(&buf[..size], data);
+    assert_eq!(received.source.addr, Some(0x20));
+    timeout(Duration::from_millis(100), a.recv_event())
+        .await
+        .unwrap()
+        .unwrap();
+}
@@ -0,0 +1,48 @@
+use super::LinkMonitor;
+use crate::socket::tests::{ifname, LOCK};
+use crate::{CanLink, LinkEvent};
+use futures_core::Stream;
+use std::future::poll_fn;
+use std::pin::Pin;
+use std::time::Duration;
+use tokio::time::timeout;
+
+macro_rules! lock {
+    (shared) => {
+        let _lock = LOCK.read();
+    };
+    (exclusive) => {
+        let _lock = LOCK.write();
+    };
+}
+
+#[tokio::test]
+async fn test_recv_nothing() {
+    let mut monitor = LinkMonitor::new().unwrap();
+    assert!(timeout(Duration::from_millis(100), monitor.recv())
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_stream() {
+    lock!(exclusive);
+    let link = CanLink::new(ifname()).unwrap();
+    let mut monitor = LinkMonitor::new().unwrap();
+    link.set_up(false).unwrap();
+    link.set_up(true).unwrap();
+
+    let event = timeout(
+        Duration::from_millis(100),
+        poll_fn(|cx| Pin::new(&mut monitor).poll_next(cx)),
+    )
+    .await
+    .unwrap()
+    .unwrap()
+    .unwrap();
+    match event {
+        LinkEvent::Changed(status) => assert_eq!(status.interface.index, link.ifindex()),
+        event => panic!("{:?}", event),
+    }
+}
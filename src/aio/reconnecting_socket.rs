@@ -0,0 +1,128 @@
+use super::{InterfaceWatcher, Socket};
+use crate::reconnecting_socket::{appeared, Connection, Reconnecting, State};
+use crate::{Error, Frame, ReceivedFrame, Result, SocketEvent, Timestamping};
+use std::ffi::CStr;
+use std::io;
+use tokio::io::Interest;
+
+/// An asynchronous [`ReconnectingSocket`](crate::ReconnectingSocket).
+pub struct ReconnectingSocket(Reconnecting<Socket>);
+
+impl ReconnectingSocket {
+    /// Succeeds even if the interface does not exist yet, waiting for it on [`recv`](Self::recv).
+    pub fn bind<I>(ifname: I) -> Result<Self>
+    where
+        I: AsRef<CStr>,
+    {
+        Reconnecting::bind(ifname).map(Self)
+    }
+
+    pub fn ifname(&self) -> &CStr {
+        &self.0.ifname
+    }
+
+    /// Returns the socket bound to the interface, or `None` while disconnected.
+    pub fn socket(&self) -> Option<&Socket> {
+        self.0.socket()
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.0.socket().is_some()
+    }
+
+    pub fn set_timestamping(&mut self, timestamping: Timestamping) -> Result<()> {
+        self.0.set_timestamping(timestamping)
+    }
+
+    pub fn set_recv_own_msgs(&mut self, enable: bool) -> Result<()> {
+        self.0.set_recv_own_msgs(enable)
+    }
+
+    pub fn set_fd_frames(&mut self, enable: bool) -> Result<()> {
+        self.0.set_fd_frames(enable)
+    }
+
+    /// Enables CAN FD frames if the interface supports them, again on every reconnection.
+    /// Returns whether CAN FD frames are enabled, `false` while disconnected.
+    pub fn set_fd_frames_if_supported(&mut self) -> Result<bool> {
+        self.0.set_fd_frames_if_supported()
+    }
+
+    pub fn set_rxq_ovfl(&mut self, enable: bool) -> Result<()> {
+        self.0.set_rxq_ovfl(enable)
+    }
+
+    /// Receives a frame, or waits until the interface returns while disconnected.
+    pub async fn recv(&mut self) -> Result<SocketEvent> {
+        loop {
+            let inner = &mut self.0;
+            let event = match &mut inner.state {
+                State::Connected(socket) => {
+                    let result = recv_frame(socket).await;
+                    inner.received(result)?
+                }
+                State::Disconnected { reported, .. } if !*reported => {
+                    *reported = true;
+                    Some(SocketEvent::Disconnected)
+                }
+                State::Disconnected { since, watcher, .. } => {
+                    let watcher = match watcher {
+                        Some(watcher) => watcher,
+                        // subscribe before binding not to miss the interface in between
+                        None => watcher.insert(InterfaceWatcher::new()?),
+                    };
+                    let since = *since;
+                    match inner.options.bind(&inner.ifname)? {
+                        Some(socket) => Some(inner.reconnected(socket, since)?),
+                        None => {
+                            while !appeared(watcher.recv().await, &inner.ifname)? {}
+                            None
+                        }
+                    }
+                }
+            };
+            if let Some(event) = event {
+                return Ok(event);
+            }
+        }
+    }
+
+    /// Fails with [`Error::InterfaceNotFound`] while disconnected.
+    pub async fn send(&mut self, frame: &Frame) -> Result<()> {
+        let result = match self.0.socket() {
+            Some(socket) => Some(socket.send(frame).await),
+            None => None,
+        };
+        self.0.sent(result)
+    }
+}
+
+impl Connection for Socket {
+    type Watcher = InterfaceWatcher;
+
+    fn connect(socket: crate::Socket) -> Result<Self> {
+        Socket::new(socket)
+    }
+
+    fn get(&self) -> &crate::Socket {
+        self.inner.get_ref()
+    }
+}
+
+// Socket::recv_frame, also woken by the error on removal of the interface,
+// which makes the socket report EPOLLERR but not readable
+async fn recv_frame(socket: &Socket) -> Result<ReceivedFrame> {
+    loop {
+        if let Ok(v) = socket
+            .inner
+            .ready(Interest::READABLE | Interest::ERROR)
+            .await?
+            .try_io(|s| s.get_ref().recv_frame().map_err(io::Error::from))
+        {
+            break v.map_err(Error::from);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,54 @@
+use super::ReconnectingSocket;
+use crate::socket::tests::{create_vcan, random_data_standard, random_ifname};
+use crate::{Error, SocketEvent};
+use std::time::Duration;
+use tokio::time::{sleep, timeout};
+
+#[tokio::test]
+async fn test_bind_not_found() {
+    let mut socket = ReconnectingSocket::bind(random_ifname("vcantest")).unwrap();
+    assert!(!socket.is_connected());
+    assert!(matches!(
+        socket.send(&random_data_standard()).await,
+        Err(Error::InterfaceNotFound { .. })
+    ));
+    assert!(timeout(Duration::from_millis(100), socket.recv())
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_reconnect() {
+    let name = random_ifname("vcantest");
+    let vcan = create_vcan(&name);
+    let mut socket = ReconnectingSocket::bind(&name).unwrap();
+    socket.set_recv_own_msgs(true).unwrap();
+
+    vcan.delete().unwrap();
+    let event = timeout(Duration::from_millis(100), socket.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(matches!(event, SocketEvent::Disconnected));
+
+    let (event, vcan) = tokio::join!(socket.recv(), async {
+        sleep(Duration::from_millis(100)).await;
+        create_vcan(&name)
+    });
+    match event.unwrap() {
+        SocketEvent::Reconnected { ifindex, gap } => {
+            assert_eq!(ifindex, vcan.ifindex());
+            assert!(gap >= Duration::from_millis(100));
+        }
+        event => panic!("{:?}", event),
+    }
+
+    // recv_own_msgs is re-applied
+    let frame = random_data_standard();
+    socket.send(&frame).await.unwrap();
+    match socket.recv().await.unwrap() {
+        SocketEvent::Frame(received) => assert_eq!(received.frame, frame),
+        event => panic!("{:?}", event),
+    }
+}
@@ -0,0 +1,243 @@
+use super::Socket;
+use crate::{Error, Frame, Result};
+use std::collections::HashMap;
+use std::future::poll_fn;
+use std::io;
+use std::mem;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+/// Sends requests and awaits responses matching a predicate, shared by many tasks.
+///
+/// The matcher of a request is registered before the request is sent,
+/// so responses arriving immediately are not missed.
+/// Received frames are dispatched to the matchers by [`run`](Self::run),
+/// which must be running (e.g. spawned) while requests are pending.
+/// A frame is given to every pending request it matches, and discarded if none.
+/// If `run` fails or is cancelled, the pending requests fail with its error.
+///
+/// ```no_run
+/// # async fn f() -> socketcan_alt::Result<()> {
+/// use socketcan_alt::aio::{Requester, Socket};
+/// use socketcan_alt::{DataFrame, Frame, Id};
+/// use std::ffi::CString;
+/// use std::time::Duration;
+///
+/// let requester = Requester::new(Socket::bind(CString::new("vcan0").unwrap())?);
+/// tokio::spawn({
+///     let requester = requester.clone();
+///     async move { requester.run().await }
+/// });
+///
+/// let request = Frame::Data(DataFrame::new(Id::Standard(0x7df), &[0x02, 0x01, 0x00]));
+/// let response = requester
+///     .request(&request, Duration::from_millis(100), |frame| {
+///         matches!(frame, Frame::Data(frame) if frame.id() == Id::Standard(0x7e8))
+///     })
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Requester(Arc<Inner>);
+
+struct Inner {
+    socket: Socket,
+    pending: Mutex<Pending>,
+}
+
+#[derive(Default)]
+struct Pending {
+    next_id: u64,
+    requests: HashMap<u64, Entry>,
+    // the error of `run` since it stopped
+    stopped: Option<Error>,
+}
+
+struct Entry {
+    matcher: Box<dyn FnMut(&Frame) -> bool + Send>,
+    max: Option<usize>,
+    responses: Vec<Frame>,
+    waker: Option<Waker>,
+}
+
+impl Requester {
+    pub fn new(socket: Socket) -> Self {
+        Self(Arc::new(Inner {
+            socket,
+            pending: Mutex::default(),
+        }))
+    }
+
+    pub fn socket(&self) -> &Socket {
+        &self.0.socket
+    }
+
+    /// Receives frames and dispatches them to the pending requests.
+    /// Runs until receiving fails.
+    pub async fn run(&self) -> Result<()> {
+        lock(&self.0.pending).stopped = None;
+        let mut running = Running {
+            inner: &self.0,
+            error: None,
+        };
+        loop {
+            match self.0.socket.recv().await {
+                Ok(frame) => lock(&self.0.pending).dispatch(&frame),
+                Err(e) => {
+                    running.error = Some(copy_error(&e));
+                    return Err(e);
+                }
+            }
+        }
+    }
+
+    /// Sends a frame and returns the first response matching the predicate.
+    /// Fails with [`io::ErrorKind::TimedOut`] if none arrives within the timeout.
+    pub async fn request<F>(&self, frame: &Frame, timeout: Duration, matcher: F) -> Result<Frame>
+    where
+        F: FnMut(&Frame) -> bool + Send + 'static,
+    {
+        self.request_many(frame, Some(1), timeout, matcher)
+            .await?
+            .pop()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::TimedOut, "no response").into())
+    }
+
+    /// Sends a frame and collects the responses matching the predicate
+    /// until `max` responses arrive or the timeout elapses.
+    pub async fn request_many<F>(
+        &self,
+        frame: &Frame,
+        max: Option<usize>,
+        timeout: Duration,
+        matcher: F,
+    ) -> Result<Vec<Frame>>
+    where
+        F: FnMut(&Frame) -> bool + Send + 'static,
+    {
+        let registration = Registration::new(&self.0, Box::new(matcher), max);
+        self.0.socket.send(frame).await?;
+        let _ = tokio::time::timeout(timeout, poll_fn(|cx| registration.poll_done(cx))).await;
+        registration.take()
+    }
+}
+
+// locks the pending requests, also after a matcher panicked while they were locked,
+// as they are left consistent
+fn lock(pending: &Mutex<Pending>) -> MutexGuard<'_, Pending> {
+    pending.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+// the error given to each pending request, as Error is not Clone
+fn copy_error(e: &Error) -> Error {
+    match e.raw_os_error() {
+        Some(errno) => io::Error::from_raw_os_error(errno).into(),
+        None => io::Error::new(e.kind(), e.to_string()).into(),
+    }
+}
+
+// fails the pending requests when `run` returns or is cancelled
+struct Running<'a> {
+    inner: &'a Inner,
+    error: Option<Error>,
+}
+
+impl Drop for Running<'_> {
+    fn drop(&mut self) {
+        let error = self.error.take().unwrap_or_else(|| {
+            io::Error::new(io::ErrorKind::NotConnected, "requester stopped running").into()
+        });
+        lock(&self.inner.pending).stop(error);
+    }
+}
+
+impl Pending {
+    fn stop(&mut self, error: Error) {
+        self.stopped = Some(error);
+        for entry in self.requests.values_mut() {
+            if let Some(waker) = entry.waker.take() {
+                waker.wake();
+            }
+        }
+    }
+
+    fn dispatch(&mut self, frame: &Frame) {
+        for entry in self.requests.values_mut() {
+            if !entry.is_full() && (entry.matcher)(frame) {
+                entry.responses.push(*frame);
+                if entry.is_full() {
+                    if let Some(waker) = entry.waker.take() {
+                        waker.wake();
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Entry {
+    fn is_full(&self) -> bool {
+        matches!(self.max, Some(max) if self.responses.len() >= max)
+    }
+}
+
+// a pending request, unregistered on drop, e.g. when the request is cancelled
+struct Registration<'a> {
+    inner: &'a Inner,
+    id: u64,
+}
+
+impl<'a> Registration<'a> {
+    fn new(
+        inner: &'a Inner,
+        matcher: Box<dyn FnMut(&Frame) -> bool + Send>,
+        max: Option<usize>,
+    ) -> Self {
+        let mut pending = lock(&inner.pending);
+        let id = pending.next_id;
+        pending.next_id += 1;
+        pending.requests.insert(
+            id,
+            Entry {
+                matcher,
+                max,
+                responses: Vec::new(),
+                waker: None,
+            },
+        );
+        Self { inner, id }
+    }
+
+    // ready when the request is full or `run` stopped
+    fn poll_done(&self, cx: &mut Context<'_>) -> Poll<()> {
+        let pending = &mut *lock(&self.inner.pending);
+        let entry = pending.requests.get_mut(&self.id).unwrap();
+        if entry.is_full() || pending.stopped.is_some() {
+            Poll::Ready(())
+        } else {
+            entry.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+
+    // the responses, or the error of `run` if it stopped before the request was full
+    fn take(&self) -> Result<Vec<Frame>> {
+        let pending = &mut *lock(&self.inner.pending);
+        let entry = pending.requests.get_mut(&self.id).unwrap();
+        match &pending.stopped {
+            Some(e) if !entry.is_full() => Err(copy_error(e)),
+            _ => Ok(mem::take(&mut entry.responses)),
+        }
+    }
+}
+
+impl Drop for Registration<'_> {
+    fn drop(&mut self) {
+        lock(&self.inner.pending).requests.remove(&self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,184 @@
+use super::{lock, Entry, Pending, Requester};
+use crate::aio::Socket;
+use crate::socket::tests::{ifname, LOCK};
+use crate::{DataFrame, Frame, Id};
+use std::io::{self, ErrorKind};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Wake, Waker};
+use std::time::{Duration, Instant};
+
+macro_rules! lock {
+    (shared) => {
+        let _lock = LOCK.read();
+    };
+    (exclusive) => {
+        let _lock = LOCK.write();
+    };
+}
+
+struct CountWaker(AtomicUsize);
+
+impl Wake for CountWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn frame(id: u32) -> Frame {
+    Frame::Data(DataFrame::new(Id::Standard(id), &[id as u8]))
+}
+
+fn id(frame: &Frame) -> Option<u32> {
+    match frame {
+        Frame::Data(frame) => match frame.id() {
+            Id::Standard(id) => Some(id),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn entry(ids: &'static [u32], max: Option<usize>, waker: &Arc<CountWaker>) -> Entry {
+    Entry {
+        matcher: Box::new(move |frame| matches!(id(frame), Some(id) if ids.contains(&id))),
+        max,
+        responses: Vec::new(),
+        waker: Some(Waker::from(waker.clone())),
+    }
+}
+
+#[test]
+fn test_dispatch() {
+    let wakers = [0, 1, 2].map(|_| Arc::new(CountWaker(AtomicUsize::new(0))));
+    let mut pending = Pending::default();
+    pending.requests.insert(0, entry(&[1], Some(1), &wakers[0]));
+    pending
+        .requests
+        .insert(1, entry(&[1, 2], Some(2), &wakers[1]));
+    pending.requests.insert(2, entry(&[1, 2], None, &wakers[2]));
+
+    for id in [3, 1, 1, 2] {
+        pending.dispatch(&frame(id));
+    }
+    let responses = |id| pending.requests[&id].responses.clone();
+    assert_eq!(responses(0), [frame(1)]);
+    assert_eq!(responses(1), [frame(1), frame(1)]);
+    assert_eq!(responses(2), [frame(1), frame(1), frame(2)]);
+    // woken only when full
+    let woken = wakers
+        .iter()
+        .map(|waker| waker.0.load(Ordering::SeqCst))
+        .collect::<Vec<_>>();
+    assert_eq!(woken, [1, 1, 0]);
+}
+
+#[test]
+fn test_stop() {
+    let wakers = [0, 1].map(|_| Arc::new(CountWaker(AtomicUsize::new(0))));
+    let mut pending = Pending::default();
+    pending.requests.insert(0, entry(&[1], Some(1), &wakers[0]));
+    pending.requests.insert(1, entry(&[1], None, &wakers[1]));
+
+    pending.stop(io::Error::from_raw_os_error(libc::ENETDOWN).into());
+    assert_eq!(
+        pending.stopped.as_ref().unwrap().kind(),
+        io::Error::from_raw_os_error(libc::ENETDOWN).kind()
+    );
+    // all woken, full or not
+    let woken = wakers
+        .iter()
+        .map(|waker| waker.0.load(Ordering::SeqCst))
+        .collect::<Vec<_>>();
+    assert_eq!(woken, [1, 1]);
+}
+
+#[test]
+fn test_lock_poisoned() {
+    let waker = Arc::new(CountWaker(AtomicUsize::new(0)));
+    let pending = Mutex::new(Pending::default());
+    let mut panicking = entry(&[], None, &waker);
+    panicking.matcher = Box::new(|_| panic!());
+    lock(&pending).requests.insert(0, panicking);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| lock(&pending).dispatch(&frame(1))));
+    assert!(result.is_err());
+    assert!(pending.is_poisoned());
+    assert!(lock(&pending).requests.remove(&0).is_some());
+    lock(&pending).requests.insert(1, entry(&[1], None, &waker));
+    lock(&pending).dispatch(&frame(1));
+    assert_eq!(lock(&pending).requests[&1].responses, [frame(1)]);
+}
+
+// responds to a request of ID n with n + 1 and n + 2
+async fn responder(socket: Socket) {
+    loop {
+        let request = socket.recv().await.unwrap();
+        if let Some(id) = id(&request).filter(|id| id % 8 == 0) {
+            socket.send(&frame(id + 1)).await.unwrap();
+            socket.send(&frame(id + 2)).await.unwrap();
+        }
+    }
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_request() {
+    lock!(shared);
+    let requester = Requester::new(Socket::bind(ifname()).unwrap());
+    let responder = tokio::spawn(responder(Socket::bind(ifname()).unwrap()));
+    let run = tokio::spawn({
+        let requester = requester.clone();
+        async move { requester.run().await }
+    });
+
+    let requests = (1..=4).map(|i| {
+        let requester = requester.clone();
+        tokio::spawn(async move {
+            let response = requester
+                .request(&frame(i * 8), Duration::from_millis(100), move |frame| {
+                    id(frame) == Some(i * 8 + 1)
+                })
+                .await
+                .unwrap();
+            assert_eq!(response, frame(i * 8 + 1));
+        })
+    });
+    for request in requests.collect::<Vec<_>>() {
+        request.await.unwrap();
+    }
+
+    let responses = requester
+        .request_many(&frame(40), None, Duration::from_millis(100), |frame| {
+            matches!(id(frame), Some(41) | Some(42))
+        })
+        .await
+        .unwrap();
+    assert_eq!(responses, [frame(41), frame(42)]);
+
+    responder.abort();
+    run.abort();
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_request_timeout() {
+    lock!(shared);
+    let requester = Requester::new(Socket::bind(ifname()).unwrap());
+    let run = tokio::spawn({
+        let requester = requester.clone();
+        async move { requester.run().await }
+    });
+
+    let start = Instant::now();
+    let e = requester
+        .request(&frame(1), Duration::from_millis(100), |_| false)
+        .await
+        .unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::TimedOut);
+    assert!(start.elapsed() >= Duration::from_millis(100));
+    assert!(requester.0.pending.lock().unwrap().requests.is_empty());
+
+    run.abort();
+}
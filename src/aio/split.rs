@@ -0,0 +1,224 @@
+use super::{poll_recv, poll_send_pending, recv, recv_frame, recv_msg, send};
+use super::{ReceivedFrames, Socket};
+use crate::{CmsgIter, Error, Frame, ReceivedFrame, Result};
+use futures_core::Stream;
+use futures_sink::Sink;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio::io::unix::AsyncFd;
+
+/// The receiving half of a [`Socket`], made by [`Socket::split`].
+pub struct ReadHalf<'a>(&'a AsyncFd<crate::Socket>);
+
+/// The sending half of a [`Socket`], made by [`Socket::split`].
+///
+/// A frame given to the [`Sink`] but not flushed stays with the socket when the half is dropped,
+/// and is sent on the next flush of the socket or of another sending half.
+pub struct WriteHalf<'a> {
+    inner: &'a AsyncFd<crate::Socket>,
+    sending: &'a mut Option<Frame>,
+}
+
+/// The owned receiving half of a [`Socket`], made by [`Socket::into_split`].
+pub struct OwnedReadHalf(Arc<Socket>);
+
+/// The owned sending half of a [`Socket`], made by [`Socket::into_split`].
+///
+/// A frame given to the [`Sink`] but not flushed is dropped with the half.
+pub struct OwnedWriteHalf {
+    socket: Arc<Socket>,
+    sending: Option<Frame>,
+}
+
+pub(super) fn split(socket: &mut Socket) -> (ReadHalf<'_>, WriteHalf<'_>) {
+    (
+        ReadHalf(&socket.inner),
+        WriteHalf {
+            inner: &socket.inner,
+            sending: &mut socket.sending,
+        },
+    )
+}
+
+pub(super) fn into_split(mut socket: Socket) -> (OwnedReadHalf, OwnedWriteHalf) {
+    let sending = socket.sending.take();
+    let socket = Arc::new(socket);
+    (
+        OwnedReadHalf(socket.clone()),
+        OwnedWriteHalf { socket, sending },
+    )
+}
+
+impl ReadHalf<'_> {
+    pub async fn recv(&self) -> Result<Frame> {
+        recv(self.0).await
+    }
+
+    pub async fn recv_msg<'b>(
+        &self,
+        cmsg_buf: &'b mut [u8],
+    ) -> Result<(Frame, Option<CmsgIter<'b>>)> {
+        recv_msg(self.0, cmsg_buf).await
+    }
+
+    pub async fn recv_frame(&self) -> Result<ReceivedFrame> {
+        recv_frame(self.0).await
+    }
+
+    pub fn received_frames(&self) -> ReceivedFrames<'_> {
+        ReceivedFrames(self.0)
+    }
+}
+
+impl WriteHalf<'_> {
+    pub async fn send(&self, frame: &Frame) -> Result<()> {
+        send(self.inner, frame).await
+    }
+}
+
+impl OwnedReadHalf {
+    pub async fn recv(&self) -> Result<Frame> {
+        self.0.recv().await
+    }
+
+    pub async fn recv_msg<'b>(
+        &self,
+        cmsg_buf: &'b mut [u8],
+    ) -> Result<(Frame, Option<CmsgIter<'b>>)> {
+        self.0.recv_msg(cmsg_buf).await
+    }
+
+    pub async fn recv_frame(&self) -> Result<ReceivedFrame> {
+        self.0.recv_frame().await
+    }
+
+    pub fn received_frames(&self) -> ReceivedFrames<'_> {
+        self.0.received_frames()
+    }
+
+    /// Restores the socket, failing if the halves are not from the same socket.
+    pub fn reunite(self, other: OwnedWriteHalf) -> Result<Socket, ReuniteError> {
+        reunite(self, other)
+    }
+}
+
+impl OwnedWriteHalf {
+    pub async fn send(&self, frame: &Frame) -> Result<()> {
+        self.socket.send(frame).await
+    }
+
+    /// Restores the socket, failing if the halves are not from the same socket.
+    pub fn reunite(self, other: OwnedReadHalf) -> Result<Socket, ReuniteError> {
+        reunite(other, self)
+    }
+}
+
+fn reunite(read: OwnedReadHalf, write: OwnedWriteHalf) -> Result<Socket, ReuniteError> {
+    if !Arc::ptr_eq(&read.0, &write.socket) {
+        return Err(ReuniteError(read, write));
+    }
+    let OwnedWriteHalf { socket, sending } = write;
+    drop(socket);
+    let mut socket = Arc::try_unwrap(read.0)
+        .unwrap_or_else(|_| unreachable!("both halves of the socket were given"));
+    socket.sending = sending;
+    Ok(socket)
+}
+
+impl Stream for ReadHalf<'_> {
+    type Item = Result<Frame>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        poll_recv(self.0, cx).map(Some)
+    }
+}
+
+impl Stream for OwnedReadHalf {
+    type Item = Result<Frame>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        poll_recv(&self.0.inner, cx).map(Some)
+    }
+}
+
+impl Sink<Frame> for WriteHalf<'_> {
+    type Error = Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let this = self.get_mut();
+        poll_send_pending(this.inner, this.sending, cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, frame: Frame) -> Result<()> {
+        *self.get_mut().sending = Some(frame);
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let this = self.get_mut();
+        poll_send_pending(this.inner, this.sending, cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let this = self.get_mut();
+        poll_send_pending(this.inner, this.sending, cx)
+    }
+}
+
+impl Sink<Frame> for OwnedWriteHalf {
+    type Error = Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let this = self.get_mut();
+        poll_send_pending(&this.socket.inner, &mut this.sending, cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, frame: Frame) -> Result<()> {
+        self.get_mut().sending = Some(frame);
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let this = self.get_mut();
+        poll_send_pending(&this.socket.inner, &mut this.sending, cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let this = self.get_mut();
+        poll_send_pending(&this.socket.inner, &mut this.sending, cx)
+    }
+}
+
+impl AsRef<Socket> for OwnedReadHalf {
+    fn as_ref(&self) -> &Socket {
+        &self.0
+    }
+}
+
+impl AsRef<Socket> for OwnedWriteHalf {
+    fn as_ref(&self) -> &Socket {
+        &self.socket
+    }
+}
+
+/// The halves given to `reunite` were not from the same socket.
+pub struct ReuniteError(pub OwnedReadHalf, pub OwnedWriteHalf);
+
+impl fmt::Debug for ReuniteError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("ReuniteError(..)")
+    }
+}
+
+impl fmt::Display for ReuniteError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("tried to reunite halves that are not from the same socket")
+    }
+}
+
+impl std::error::Error for ReuniteError {}
+
+#[cfg(test)]
+mod tests;
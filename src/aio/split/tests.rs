@@ -0,0 +1,113 @@
+use super::super::Socket;
+use crate::socket::tests::{ifname, random_data_standard, LOCK};
+use futures_util::{SinkExt, StreamExt};
+use std::ffi::CString;
+use std::time::Duration;
+use tokio::time::timeout;
+
+macro_rules! lock {
+    (shared) => {
+        let _lock = LOCK.read();
+    };
+    (exclusive) => {
+        let _lock = LOCK.write();
+    };
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_split() {
+    lock!(shared);
+    let mut socket = Socket::bind(ifname()).unwrap();
+    socket.set_recv_own_msgs(true).unwrap();
+    let (mut read, write) = socket.split();
+
+    let frame = random_data_standard();
+    let (received, sent) = timeout(Duration::from_millis(100), async {
+        tokio::join!(read.next(), write.send(&frame))
+    })
+    .await
+    .unwrap();
+    sent.unwrap();
+    assert_eq!(received.unwrap().unwrap(), frame);
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_split_keeps_unflushed_frame() {
+    lock!(shared);
+    let mut socket = Socket::bind(ifname()).unwrap();
+    socket.set_recv_own_msgs(true).unwrap();
+    let frame = random_data_standard();
+    {
+        let (_, mut write) = socket.split();
+        write.feed(frame).await.unwrap();
+    }
+
+    socket.flush().await.unwrap();
+    let received = timeout(Duration::from_millis(100), socket.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(received, frame);
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_into_split() {
+    lock!(shared);
+    let socket = Socket::bind(ifname()).unwrap();
+    socket.set_recv_own_msgs(true).unwrap();
+    let (read, write) = socket.into_split();
+
+    let frame = random_data_standard();
+    let receiver = tokio::spawn(async move {
+        let frame = read.recv().await.unwrap();
+        (read, frame)
+    });
+    write.send(&frame).await.unwrap();
+    let (read, received) = timeout(Duration::from_millis(100), receiver)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(received, frame);
+
+    let socket = read.reunite(write).unwrap();
+    assert!(socket.mtu() > 0);
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_reunite_error() {
+    lock!(shared);
+    let (read, _) = Socket::bind(ifname()).unwrap().into_split();
+    let (_, write) = Socket::bind(ifname()).unwrap().into_split();
+    let (read, write) = match read.reunite(write) {
+        Err(e) => (e.0, e.1),
+        Ok(_) => panic!(),
+    };
+    assert!(write.reunite(read).is_err());
+}
+
+#[test]
+fn test_marker_traits() {
+    fn check<F>(_: F)
+    where
+        F: Send + 'static,
+    {
+    }
+
+    check(async {
+        let ifname = CString::new("NO DEVICE").unwrap();
+        let (mut read, mut write) = Socket::bind(ifname).unwrap().into_split();
+
+        read.recv().await.unwrap();
+        read.next().await.unwrap().unwrap();
+
+        let frame = random_data_standard();
+        write.send(&frame).await.unwrap();
+        SinkExt::send(&mut write, frame).await.unwrap();
+
+        read.reunite(write).unwrap();
+    })
+}
@@ -1,6 +1,9 @@
 use super::Socket;
-use crate::socket::tests::{ifname, random_data_standard, random_fd_data_standard, LOCK};
-use crate::{Cmsg, Frame, Timestamping};
+use crate::socket::tests::{
+    create_vcan, ifname, random_data_standard, random_fd_data_standard, random_ifname, LOCK,
+};
+use crate::{Cmsg, Frame, MsgFlags, ReceivedFrame, Timestamping};
+use futures_util::{SinkExt, StreamExt};
 use std::ffi::CString;
 use std::io::ErrorKind;
 use std::io::Result;
@@ -47,6 +50,23 @@ async fn recv_msg(socket: Socket, query: Option<Frame>) -> Option<Result<Option<
     .ok()
 }
 
+async fn recv_frame(socket: Socket, query: Option<Frame>) -> Option<Result<ReceivedFrame>> {
+    timeout(Duration::from_millis(100), async {
+        loop {
+            let received = socket.recv_frame().await?;
+            if query
+                .as_ref()
+                .map(|query| &received.frame == query)
+                .unwrap_or(true)
+            {
+                return Ok(received);
+            }
+        }
+    })
+    .await
+    .ok()
+}
+
 #[tokio::test]
 #[ignore]
 async fn test_bind() {
@@ -181,6 +201,110 @@ async fn test_set_fd_frames_on() {
     recv(socket_rx, Some(frame)).await.unwrap().unwrap();
 }
 
+#[tokio::test]
+#[ignore]
+async fn test_recv_frame_enabled() {
+    lock!(shared);
+    let socket = Socket::bind(ifname()).unwrap();
+    socket.set_recv_own_msgs(true).unwrap();
+    socket
+        .set_timestamping(Timestamping::RX_SOFTWARE | Timestamping::SOFTWARE)
+        .unwrap();
+    socket.set_rxq_ovfl(true).unwrap();
+
+    let frame = random_data_standard();
+    socket.send(&frame).await.unwrap();
+    let received = recv_frame(socket, Some(frame)).await.unwrap().unwrap();
+    assert_eq!(received.flags, MsgFlags::DONTROUTE | MsgFlags::CONFIRM);
+    assert!(received.timestamps.unwrap()[0].tv_sec != 0);
+    assert_eq!(received.dropped, Some(0));
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_stream_sink() {
+    lock!(shared);
+    let mut socket_tx = Socket::bind(ifname()).unwrap();
+    let mut socket_rx = Socket::bind(ifname()).unwrap();
+
+    let frames = (0..3).map(|_| random_data_standard()).collect::<Vec<_>>();
+    for frame in &frames {
+        socket_tx.feed(*frame).await.unwrap();
+    }
+    socket_tx.flush().await.unwrap();
+    let received = timeout(
+        Duration::from_millis(100),
+        (&mut socket_rx).take(3).collect::<Vec<_>>(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        received.into_iter().map(|v| v.unwrap()).collect::<Vec<_>>(),
+        frames
+    );
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_received_frames() {
+    lock!(shared);
+    let socket_tx = Socket::bind(ifname()).unwrap();
+    let socket_rx = Socket::bind(ifname()).unwrap();
+
+    let frame = random_data_standard();
+    socket_tx.send(&frame).await.unwrap();
+    let mut received_frames = socket_rx.received_frames();
+    let received = timeout(Duration::from_millis(100), received_frames.next())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(received.frame, frame);
+    assert_eq!(
+        received.ifindex,
+        socket_rx.inner.get_ref().ifindex().unwrap()
+    );
+}
+
+#[tokio::test]
+#[ignore]
+async fn test_forward() {
+    let names = [0, 1].map(|_| random_ifname("vcantest"));
+    let _vcans = names
+        .iter()
+        .map(|name| create_vcan(name))
+        .collect::<Vec<_>>();
+    let socket_tx = Socket::bind(&names[0]).unwrap();
+    let source = Socket::bind(&names[0]).unwrap();
+    let sink = Socket::bind(&names[1]).unwrap();
+    let socket_rx = Socket::bind(&names[1]).unwrap();
+
+    let frames = (0..3).map(|_| random_data_standard()).collect::<Vec<_>>();
+    let forward = source.take(frames.len()).forward(sink);
+    let send = async {
+        for frame in &frames {
+            socket_tx.send(frame).await.unwrap();
+        }
+    };
+    let (forwarded, ()) = timeout(Duration::from_millis(100), async {
+        tokio::join!(forward, send)
+    })
+    .await
+    .unwrap();
+    forwarded.unwrap();
+
+    let received = timeout(
+        Duration::from_millis(100),
+        socket_rx.take(frames.len()).collect::<Vec<_>>(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        received.into_iter().map(|v| v.unwrap()).collect::<Vec<_>>(),
+        frames
+    );
+}
+
 #[test]
 fn test_marker_traits() {
     fn check<F>(_: F)
@@ -198,7 +322,14 @@ fn test_marker_traits() {
         let mut cmsg_buf = Vec::new();
         socket.recv_msg(&mut cmsg_buf).await.unwrap();
 
+        socket.recv_frame().await.unwrap();
+
         let frame = random_data_standard();
         socket.send(&frame).await.unwrap();
+
+        let mut socket = socket;
+        socket.next().await.unwrap().unwrap();
+        socket.received_frames().next().await.unwrap().unwrap();
+        SinkExt::send(&mut socket, frame).await.unwrap();
     })
 }
@@ -0,0 +1,227 @@
+mod flags;
+mod message;
+mod setup;
+
+use crate::socket::{address, if_nametoindex, socket};
+use crate::{sys, Error, Frame, Id, Result};
+pub use flags::BcmFlags;
+pub use message::BcmMessage;
+pub use setup::{RxSetup, TxSetup};
+use std::ffi::CStr;
+use std::mem::{self, size_of, size_of_val, MaybeUninit};
+use std::os::raw::c_int;
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use std::slice;
+use std::time::Duration;
+
+// MAX_NFRAMES in net/can/bcm.c
+const MAX_NFRAMES: usize = 256;
+
+/// A socket of the Broadcast Manager (`CAN_BCM`),
+/// which sends frames cyclically and filters received frames by their content in the kernel.
+pub struct BcmSocket {
+    fd: RawFd,
+}
+
+impl BcmSocket {
+    pub fn connect<I>(ifname: I) -> Result<Self>
+    where
+        I: AsRef<CStr>,
+    {
+        let ifindex = if_nametoindex(ifname)?;
+        let socket = Self {
+            fd: socket(libc::SOCK_DGRAM, sys::CAN_BCM as _, "CAN_BCM")?,
+        };
+
+        let address = address(ifindex);
+        if unsafe {
+            libc::connect(
+                socket.as_raw_fd(),
+                &address as *const _ as _,
+                size_of_val(&address) as _,
+            ) != 0
+        } {
+            return Err(Error::last_os_error());
+        }
+        Ok(socket)
+    }
+
+    pub fn set_nonblocking(&self, nonblocking: bool) -> Result<()> {
+        if unsafe { libc::ioctl(self.as_raw_fd(), libc::FIONBIO, &(nonblocking as c_int)) } != 0 {
+            return Err(Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Creates or updates a cyclic transmission.
+    pub fn tx_setup(&self, setup: &TxSetup<'_>) -> Result<()> {
+        self.write(&encode(
+            sys::TX_SETUP,
+            setup.flags,
+            setup.count,
+            (setup.ival1, setup.ival2),
+            setup.id,
+            setup.frames,
+        )?)
+    }
+
+    /// Jobs of classic frames and CAN FD frames are distinguished by `fd`.
+    pub fn tx_delete(&self, id: Id, fd: bool) -> Result<()> {
+        self.write(&encode_id(sys::TX_DELETE, id, fd)?)
+    }
+
+    /// Requests the status of a transmission, which is received as [`BcmMessage::TxStatus`].
+    pub fn tx_read(&self, id: Id, fd: bool) -> Result<()> {
+        self.write(&encode_id(sys::TX_READ, id, fd)?)
+    }
+
+    /// Creates or updates a content filter.
+    pub fn rx_setup(&self, setup: &RxSetup<'_>) -> Result<()> {
+        self.write(&encode(
+            sys::RX_SETUP,
+            setup.flags,
+            0,
+            (setup.timeout, setup.throttle),
+            setup.id,
+            setup.masks,
+        )?)
+    }
+
+    pub fn rx_delete(&self, id: Id, fd: bool) -> Result<()> {
+        self.write(&encode_id(sys::RX_DELETE, id, fd)?)
+    }
+
+    /// Requests the status of a content filter, which is received as [`BcmMessage::RxStatus`].
+    pub fn rx_read(&self, id: Id, fd: bool) -> Result<()> {
+        self.write(&encode_id(sys::RX_READ, id, fd)?)
+    }
+
+    pub fn recv(&self) -> Result<BcmMessage> {
+        // peek the head to size the buffer from the number of frames
+        let mut head = MaybeUninit::<sys::bcm_msg_head>::zeroed();
+        if unsafe {
+            libc::recv(
+                self.as_raw_fd(),
+                head.as_mut_ptr() as _,
+                size_of::<sys::bcm_msg_head>(),
+                libc::MSG_PEEK,
+            )
+        } < 0
+        {
+            return Err(Error::last_os_error());
+        }
+        let head = unsafe { head.assume_init() };
+        let nframes = (head.nframes as usize).min(MAX_NFRAMES);
+        let flags = BcmFlags::from_bits_truncate(head.flags);
+        let mut buf = vec![0; size_of::<sys::bcm_msg_head>() + nframes * frame_size(flags)];
+        let size = unsafe { libc::read(self.as_raw_fd(), buf.as_mut_ptr() as _, buf.len()) };
+        if size < 0 {
+            return Err(Error::last_os_error());
+        }
+        BcmMessage::from_bytes(&buf[..size as _])
+    }
+
+    fn write(&self, buf: &[u8]) -> Result<()> {
+        if unsafe { libc::write(self.as_raw_fd(), buf.as_ptr() as _, buf.len()) } as usize
+            != buf.len()
+        {
+            return Err(Error::last_os_error());
+        }
+        Ok(())
+    }
+}
+
+impl Drop for BcmSocket {
+    fn drop(&mut self) {
+        unsafe { libc::close(self.as_raw_fd()) };
+    }
+}
+
+impl AsRawFd for BcmSocket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd
+    }
+}
+
+impl FromRawFd for BcmSocket {
+    unsafe fn from_raw_fd(fd: RawFd) -> Self {
+        Self { fd }
+    }
+}
+
+impl IntoRawFd for BcmSocket {
+    fn into_raw_fd(self) -> RawFd {
+        let fd = self.fd;
+        mem::forget(self);
+        fd
+    }
+}
+
+// bcm_msg_head followed by the frames
+fn encode(
+    opcode: u32,
+    mut flags: BcmFlags,
+    count: u32,
+    (ival1, ival2): (Duration, Duration),
+    id: Id,
+    frames: &[Frame],
+) -> Result<Vec<u8>> {
+    if let Some(Frame::FdData(_)) = frames.first() {
+        flags |= BcmFlags::CAN_FD_FRAME;
+    }
+    let size = frame_size(flags);
+    if frames.iter().any(|frame| frame.as_bytes().len() != size) {
+        return Err(Error::InvalidFrame {
+            reason: "mixed classic and CAN FD frames",
+        });
+    }
+
+    // the bytes are taken from the zeroed head, as a typed copy would leave its padding uninitialized
+    let mut head = MaybeUninit::<sys::bcm_msg_head>::zeroed();
+    let mut buf = unsafe {
+        (*head.as_mut_ptr()).opcode = opcode;
+        (*head.as_mut_ptr()).flags = flags.bits();
+        (*head.as_mut_ptr()).count = count;
+        (*head.as_mut_ptr()).ival1 = timeval(ival1);
+        (*head.as_mut_ptr()).ival2 = timeval(ival2);
+        (*head.as_mut_ptr()).can_id = id.try_into_can_id()?;
+        (*head.as_mut_ptr()).nframes = frames.len() as _;
+        slice::from_raw_parts(head.as_ptr() as *const u8, size_of::<sys::bcm_msg_head>())
+    }
+    .to_vec();
+    for frame in frames {
+        buf.extend_from_slice(frame.as_bytes());
+    }
+    Ok(buf)
+}
+
+fn encode_id(opcode: u32, id: Id, fd: bool) -> Result<Vec<u8>> {
+    let flags = if fd {
+        BcmFlags::CAN_FD_FRAME
+    } else {
+        BcmFlags::empty()
+    };
+    encode(opcode, flags, 0, (Duration::ZERO, Duration::ZERO), id, &[])
+}
+
+fn frame_size(flags: BcmFlags) -> usize {
+    if flags.contains(BcmFlags::CAN_FD_FRAME) {
+        sys::CANFD_MTU
+    } else {
+        sys::CAN_MTU
+    }
+}
+
+fn timeval(duration: Duration) -> sys::bcm_timeval {
+    sys::bcm_timeval {
+        tv_sec: duration.as_secs() as _,
+        tv_usec: duration.subsec_micros() as _,
+    }
+}
+
+fn duration(timeval: sys::bcm_timeval) -> Duration {
+    Duration::new(timeval.tv_sec as _, timeval.tv_usec as u32 * 1000)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,31 @@
+use crate::sys;
+
+bitflags::bitflags! {
+    /// Flags of BCM jobs.
+    /// `SETTIMER`, `STARTTIMER` and `CAN_FD_FRAME` are set by [`TxSetup`](crate::TxSetup)
+    /// and [`RxSetup`](crate::RxSetup) as needed.
+    pub struct BcmFlags: u32 {
+        const SETTIMER = sys::SETTIMER;
+        const STARTTIMER = sys::STARTTIMER;
+        /// Notifies [`BcmMessage::TxExpired`](crate::BcmMessage::TxExpired)
+        /// when the first phase of a transmission is finished.
+        const TX_COUNTEVT = sys::TX_COUNTEVT;
+        /// Sends the frames immediately when a job is updated.
+        const TX_ANNOUNCE = sys::TX_ANNOUNCE;
+        /// Sends the frames with the identifier of the job.
+        const TX_CP_CAN_ID = sys::TX_CP_CAN_ID;
+        /// Notifies any frame with the identifier, ignoring the masks.
+        const RX_FILTER_ID = sys::RX_FILTER_ID;
+        /// Notifies changes of the data length as well.
+        const RX_CHECK_DLC = sys::RX_CHECK_DLC;
+        /// Does not reset the timeout monitoring when a timeout is notified.
+        const RX_NO_AUTOTIMER = sys::RX_NO_AUTOTIMER;
+        /// Notifies the next frame as changed after a timeout.
+        const RX_ANNOUNCE_RESUME = sys::RX_ANNOUNCE_RESUME;
+        /// Restarts the transmission from the first frame when a job is updated.
+        const TX_RESET_MULTI_IDX = sys::TX_RESET_MULTI_IDX;
+        /// Replies to remote frames with the frame of the job.
+        const RX_RTR_FRAME = sys::RX_RTR_FRAME;
+        const CAN_FD_FRAME = sys::CAN_FD_FRAME;
+    }
+}
@@ -0,0 +1,85 @@
+use super::{duration, frame_size};
+use crate::{sys, BcmFlags, Error, Frame, Id, Result};
+use std::io;
+use std::mem::size_of;
+use std::ptr;
+use std::time::Duration;
+
+/// A message sent by the kernel to a BCM socket.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BcmMessage {
+    /// Reply to [`BcmSocket::tx_read`](crate::BcmSocket::tx_read).
+    TxStatus {
+        id: Id,
+        flags: BcmFlags,
+        count: u32,
+        ival1: Duration,
+        ival2: Duration,
+        frames: Vec<Frame>,
+    },
+    /// The first phase of a transmission with `TX_COUNTEVT` is finished.
+    TxExpired { id: Id },
+    /// Reply to [`BcmSocket::rx_read`](crate::BcmSocket::rx_read).
+    RxStatus {
+        id: Id,
+        flags: BcmFlags,
+        timeout: Duration,
+        throttle: Duration,
+        masks: Vec<Frame>,
+    },
+    /// No frame was received within the timeout.
+    RxTimeout { id: Id },
+    /// A received frame whose data changed.
+    RxChanged { id: Id, frame: Frame },
+}
+
+impl BcmMessage {
+    pub(super) fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < size_of::<sys::bcm_msg_head>() {
+            return Err(invalid_data());
+        }
+        let head = unsafe { ptr::read_unaligned(bytes.as_ptr() as *const sys::bcm_msg_head) };
+        let flags = BcmFlags::from_bits_truncate(head.flags);
+        let id = Id::from_can_id(head.can_id);
+
+        let frames = &bytes[size_of::<sys::bcm_msg_head>()..];
+        let size = frame_size(flags);
+        if frames.len() != head.nframes as usize * size {
+            return Err(invalid_data());
+        }
+        let mut frames = frames
+            .chunks(size)
+            .map(Frame::from_bytes)
+            .collect::<Result<Vec<_>>>()?;
+
+        match head.opcode {
+            sys::TX_STATUS => Ok(Self::TxStatus {
+                id,
+                flags,
+                count: head.count,
+                ival1: duration(head.ival1),
+                ival2: duration(head.ival2),
+                frames,
+            }),
+            sys::TX_EXPIRED => Ok(Self::TxExpired { id }),
+            sys::RX_STATUS => Ok(Self::RxStatus {
+                id,
+                flags,
+                timeout: duration(head.ival1),
+                throttle: duration(head.ival2),
+                masks: frames,
+            }),
+            sys::RX_TIMEOUT => Ok(Self::RxTimeout { id }),
+            sys::RX_CHANGED if frames.len() == 1 => Ok(Self::RxChanged {
+                id,
+                frame: frames.pop().unwrap(),
+            }),
+            _ => Err(invalid_data()),
+        }
+    }
+}
+
+fn invalid_data() -> Error {
+    io::Error::new(io::ErrorKind::InvalidData, "malformed BCM message").into()
+}
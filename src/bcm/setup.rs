@@ -0,0 +1,126 @@
+use crate::{BcmFlags, Frame, Id};
+use std::time::Duration;
+
+/// A cyclic transmission by the kernel, set up by [`BcmSocket::tx_setup`](crate::BcmSocket::tx_setup).
+///
+/// The frames are sent in turn.
+/// The first `count` frames are sent with the interval `ival1`,
+/// followed by the rest with the interval `ival2` until the job is deleted.
+/// Setting up a job with an existing identifier updates its frames,
+/// and its timers too if [`count`](Self::count) or [`interval`](Self::interval) is given.
+///
+/// ```
+/// use socketcan_alt::{DataFrame, Frame, Id, TxSetup};
+/// use std::time::Duration;
+///
+/// let frames = [Frame::Data(DataFrame::new(Id::Standard(42), &[0, 1, 2, 3]))];
+/// let setup = TxSetup::new(Id::Standard(42), &frames).interval(Duration::from_millis(100));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct TxSetup<'a> {
+    pub(super) id: Id,
+    pub(super) frames: &'a [Frame],
+    pub(super) flags: BcmFlags,
+    pub(super) count: u32,
+    pub(super) ival1: Duration,
+    pub(super) ival2: Duration,
+}
+
+impl<'a> TxSetup<'a> {
+    /// `id` identifies the job.
+    /// The frames are sent with their own identifiers unless `TX_CP_CAN_ID` is set.
+    /// They must be all classic frames or all CAN FD frames.
+    pub fn new(id: Id, frames: &'a [Frame]) -> Self {
+        Self {
+            id,
+            frames,
+            flags: BcmFlags::empty(),
+            count: 0,
+            ival1: Duration::ZERO,
+            ival2: Duration::ZERO,
+        }
+    }
+
+    /// Sends `count` frames with the interval `ival1` before switching to the interval of [`interval`](Self::interval).
+    pub fn count(mut self, count: u32, ival1: Duration) -> Self {
+        self.count = count;
+        self.ival1 = ival1;
+        self.flags |= BcmFlags::SETTIMER | BcmFlags::STARTTIMER;
+        self
+    }
+
+    /// Zero stops the transmission after the first phase.
+    pub fn interval(mut self, ival2: Duration) -> Self {
+        self.ival2 = ival2;
+        self.flags |= BcmFlags::SETTIMER | BcmFlags::STARTTIMER;
+        self
+    }
+
+    pub fn flags(mut self, flags: BcmFlags) -> Self {
+        self.flags |= flags;
+        self
+    }
+}
+
+/// A content filter of received frames by the kernel, set up by [`BcmSocket::rx_setup`](crate::BcmSocket::rx_setup).
+///
+/// Frames with the identifier are notified as [`BcmMessage::RxChanged`](crate::BcmMessage::RxChanged)
+/// when their data change within the masks.
+/// Without masks, every frame with the identifier is notified.
+#[derive(Clone, Copy, Debug)]
+pub struct RxSetup<'a> {
+    pub(super) id: Id,
+    pub(super) masks: &'a [Frame],
+    pub(super) flags: BcmFlags,
+    pub(super) timeout: Duration,
+    pub(super) throttle: Duration,
+}
+
+impl<'a> RxSetup<'a> {
+    pub fn new(id: Id) -> Self {
+        Self {
+            id,
+            masks: &[],
+            flags: BcmFlags::RX_FILTER_ID,
+            timeout: Duration::ZERO,
+            throttle: Duration::ZERO,
+        }
+    }
+
+    /// The data of `masks` select the bits to be compared.
+    /// With multiple masks, the first one selects the bits of the multiplex index,
+    /// and the received frame is compared with the mask whose index matches.
+    /// They must be all classic frames or all CAN FD frames.
+    /// An empty slice filters by the identifier only.
+    pub fn masks(mut self, masks: &'a [Frame]) -> Self {
+        self.masks = masks;
+        self.flags.set(BcmFlags::RX_FILTER_ID, masks.is_empty());
+        self
+    }
+
+    /// Receives CAN FD frames. Implied by CAN FD masks.
+    pub fn fd(mut self) -> Self {
+        self.flags |= BcmFlags::CAN_FD_FRAME;
+        self
+    }
+
+    /// Notifies [`BcmMessage::RxTimeout`](crate::BcmMessage::RxTimeout)
+    /// when no frame is received within `timeout`.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self.flags |= BcmFlags::SETTIMER | BcmFlags::STARTTIMER;
+        self
+    }
+
+    /// Notifies changes at most once per `throttle`.
+    pub fn throttle(mut self, throttle: Duration) -> Self {
+        self.throttle = throttle;
+        self.flags |= BcmFlags::SETTIMER | BcmFlags::STARTTIMER;
+        self
+    }
+
+    pub fn flags(mut self, flags: BcmFlags) -> Self {
+        self.flags |= flags;
+        self
+    }
+}
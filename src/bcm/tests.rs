@@ -0,0 +1,259 @@
+use super::{encode, BcmFlags, BcmMessage, BcmSocket, RxSetup, TxSetup};
+use crate::socket::tests::{ifname, random_data_standard, timeout, LOCK};
+use crate::{sys, DataFrame, FdDataFrame, Frame, Id, Socket};
+use std::ffi::CString;
+use std::mem::size_of;
+use std::time::Duration;
+
+macro_rules! lock {
+    (shared) => {
+        let _lock = LOCK.read();
+    };
+    (exclusive) => {
+        let _lock = LOCK.write();
+    };
+}
+
+const HEAD: usize = size_of::<sys::bcm_msg_head>();
+
+#[test]
+fn test_encode() {
+    let frames = [
+        Frame::Data(DataFrame::new(Id::Standard(42), &[0, 1, 2, 3])),
+        Frame::Data(DataFrame::new(Id::Standard(43), &[4, 5, 6, 7])),
+    ];
+    let buf = encode(
+        sys::TX_SETUP,
+        BcmFlags::SETTIMER,
+        3,
+        (Duration::from_millis(1500), Duration::ZERO),
+        Id::Extended(4242),
+        &frames,
+    )
+    .unwrap();
+    assert_eq!(buf.len(), HEAD + 2 * sys::CAN_MTU);
+    assert_eq!(&buf[HEAD..HEAD + sys::CAN_MTU], frames[0].as_bytes());
+    assert_eq!(&buf[HEAD + sys::CAN_MTU..], frames[1].as_bytes());
+
+    let head = unsafe { (buf.as_ptr() as *const sys::bcm_msg_head).read_unaligned() };
+    assert_eq!(head.opcode, sys::TX_SETUP);
+    assert_eq!(head.flags, sys::SETTIMER);
+    assert_eq!(head.count, 3);
+    assert_eq!((head.ival1.tv_sec, head.ival1.tv_usec), (1, 500_000));
+    assert_eq!(head.can_id, 4242 | sys::CAN_EFF_FLAG);
+    assert_eq!(head.nframes, 2);
+}
+
+#[test]
+fn test_encode_fd() {
+    let frames = [Frame::FdData(FdDataFrame::new(
+        Id::Standard(42),
+        true,
+        false,
+        &[0; 12],
+    ))];
+    let buf = encode(
+        sys::RX_SETUP,
+        BcmFlags::empty(),
+        0,
+        (Duration::ZERO, Duration::ZERO),
+        Id::Standard(42),
+        &frames,
+    )
+    .unwrap();
+    assert_eq!(buf.len(), HEAD + sys::CANFD_MTU);
+    let head = unsafe { (buf.as_ptr() as *const sys::bcm_msg_head).read_unaligned() };
+    assert_eq!(head.flags, sys::CAN_FD_FRAME);
+}
+
+#[test]
+fn test_encode_mixed() {
+    let frames = [
+        Frame::FdData(FdDataFrame::new(Id::Standard(42), false, false, &[])),
+        Frame::Data(DataFrame::new(Id::Standard(42), &[])),
+    ];
+    assert!(encode(
+        sys::TX_SETUP,
+        BcmFlags::empty(),
+        0,
+        (Duration::ZERO, Duration::ZERO),
+        Id::Standard(42),
+        &frames,
+    )
+    .is_err());
+}
+
+#[test]
+fn test_decode() {
+    let frames = vec![random_data_standard(), random_data_standard()];
+    let flags = BcmFlags::SETTIMER | BcmFlags::TX_COUNTEVT;
+    let (ival1, ival2) = (Duration::from_micros(1234), Duration::from_secs(2));
+    let buf = encode(
+        sys::TX_STATUS,
+        flags,
+        5,
+        (ival1, ival2),
+        Id::Standard(42),
+        &frames,
+    )
+    .unwrap();
+    assert_eq!(
+        BcmMessage::from_bytes(&buf).unwrap(),
+        BcmMessage::TxStatus {
+            id: Id::Standard(42),
+            flags,
+            count: 5,
+            ival1,
+            ival2,
+            frames,
+        }
+    );
+}
+
+#[test]
+fn test_decode_rx_changed() {
+    let frame = Frame::FdData(FdDataFrame::new(Id::Extended(4242), false, true, &[1; 48]));
+    let buf = encode(
+        sys::RX_CHANGED,
+        BcmFlags::empty(),
+        0,
+        (Duration::ZERO, Duration::ZERO),
+        Id::Extended(4242),
+        &[frame],
+    )
+    .unwrap();
+    assert_eq!(
+        BcmMessage::from_bytes(&buf).unwrap(),
+        BcmMessage::RxChanged {
+            id: Id::Extended(4242),
+            frame,
+        }
+    );
+}
+
+#[test]
+fn test_decode_truncated() {
+    let frames = [random_data_standard()];
+    let buf = encode(
+        sys::RX_CHANGED,
+        BcmFlags::empty(),
+        0,
+        (Duration::ZERO, Duration::ZERO),
+        Id::Standard(42),
+        &frames,
+    )
+    .unwrap();
+    assert!(BcmMessage::from_bytes(&buf[..buf.len() - 1]).is_err());
+    assert!(BcmMessage::from_bytes(&buf[..HEAD - 1]).is_err());
+}
+
+#[test]
+#[ignore]
+fn test_connect() {
+    BcmSocket::connect(ifname()).unwrap();
+}
+
+#[test]
+fn test_connect_no_device() {
+    let ifname = CString::new("NO DEVICE").unwrap();
+    assert!(BcmSocket::connect(ifname).is_err());
+}
+
+#[test]
+#[ignore]
+fn test_tx_setup() {
+    lock!(exclusive);
+    let socket = Socket::bind(ifname()).unwrap();
+    let bcm = BcmSocket::connect(ifname()).unwrap();
+
+    let frames = [random_data_standard(), random_data_standard()];
+    bcm.tx_setup(&TxSetup::new(Id::Standard(42), &frames).interval(Duration::from_millis(10)))
+        .unwrap();
+    let received =
+        timeout(move || (0..4).map(|_| socket.recv().unwrap()).collect::<Vec<_>>()).unwrap();
+    assert_eq!(received, [frames[0], frames[1], frames[0], frames[1]]);
+}
+
+#[test]
+#[ignore]
+fn test_tx_read() {
+    lock!(shared);
+    let bcm = BcmSocket::connect(ifname()).unwrap();
+
+    let frames = [random_data_standard()];
+    bcm.tx_setup(&TxSetup::new(Id::Standard(42), &frames).count(1, Duration::from_secs(1)))
+        .unwrap();
+    bcm.tx_read(Id::Standard(42), false).unwrap();
+    match bcm.recv().unwrap() {
+        BcmMessage::TxStatus { id, frames: f, .. } => {
+            assert_eq!(id, Id::Standard(42));
+            assert_eq!(f, frames);
+        }
+        message => panic!("unexpected message: {:?}", message),
+    }
+
+    bcm.tx_delete(Id::Standard(42), false).unwrap();
+    assert!(bcm.tx_read(Id::Standard(42), false).is_err());
+}
+
+#[test]
+fn test_rx_setup_masks() {
+    let masks = [Frame::Data(DataFrame::new(Id::Standard(0), &[0xff]))];
+    let setup = RxSetup::new(Id::Standard(42)).masks(&masks);
+    assert!(!setup.flags.contains(BcmFlags::RX_FILTER_ID));
+    // RX_SETUP without frames requires RX_FILTER_ID
+    let setup = setup.masks(&[]);
+    assert!(setup.flags.contains(BcmFlags::RX_FILTER_ID));
+}
+
+#[test]
+#[ignore]
+fn test_rx_changed() {
+    lock!(exclusive);
+    let socket = Socket::bind(ifname()).unwrap();
+    let bcm = BcmSocket::connect(ifname()).unwrap();
+
+    let mask = Frame::Data(DataFrame::new(Id::Standard(0), &[0xff]));
+    bcm.rx_setup(&RxSetup::new(Id::Standard(42)).masks(&[mask]))
+        .unwrap();
+    let frame = Frame::Data(DataFrame::new(Id::Standard(42), &[1, 2]));
+    socket.send(&frame).unwrap();
+    // unchanged within the mask
+    socket
+        .send(&Frame::Data(DataFrame::new(Id::Standard(42), &[1, 3])))
+        .unwrap();
+    let frame_changed = Frame::Data(DataFrame::new(Id::Standard(42), &[2, 3]));
+    socket.send(&frame_changed).unwrap();
+
+    let received =
+        timeout(move || (0..2).map(|_| bcm.recv().unwrap()).collect::<Vec<_>>()).unwrap();
+    assert_eq!(
+        received,
+        [
+            BcmMessage::RxChanged {
+                id: Id::Standard(42),
+                frame,
+            },
+            BcmMessage::RxChanged {
+                id: Id::Standard(42),
+                frame: frame_changed,
+            },
+        ]
+    );
+}
+
+#[test]
+#[ignore]
+fn test_rx_timeout() {
+    lock!(exclusive);
+    let bcm = BcmSocket::connect(ifname()).unwrap();
+
+    bcm.rx_setup(&RxSetup::new(Id::Standard(42)).timeout(Duration::from_millis(10)))
+        .unwrap();
+    assert_eq!(
+        timeout(move || bcm.recv().unwrap()).unwrap(),
+        BcmMessage::RxTimeout {
+            id: Id::Standard(42)
+        }
+    );
+}
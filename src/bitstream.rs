@@ -0,0 +1,243 @@
+use crate::frame::{dlc_to_len, len_to_dlc};
+use crate::{sys, Frame, Id};
+use std::ops::Range;
+use std::time::Duration;
+
+const CRC15: (u32, u32, u32) = (0x4599, 15, 0);
+const CRC17: (u32, u32, u32) = (0x1_685b, 17, 1 << 16);
+const CRC21: (u32, u32, u32) = (0x10_2899, 21, 1 << 20);
+
+/// Bits of a frame as transmitted on the bus (ISO 11898-1), from SOF to the end of EOF.
+///
+/// Stuff bits are included. The ACK slot is encoded as dominant, i.e. as acknowledged.
+/// The interframe space is not included.
+/// CAN FD frames are encoded in the ISO format (with stuff count).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bitstream {
+    bits: Vec<bool>,
+    data_phase: Range<usize>,
+    stuff_bits: usize,
+}
+
+impl Bitstream {
+    /// Returns `None` for error frames, which are not transmitted on the bus.
+    pub fn new(frame: &Frame) -> Option<Self> {
+        let mut encoder = Encoder::default();
+        match frame {
+            Frame::Data(frame) => {
+                encoder.arbitration(frame.id(), false);
+                encoder.classic_control(frame.data().len());
+                encoder.data(frame.data());
+                Some(encoder.finish_classic())
+            }
+            Frame::Remote(frame) => {
+                encoder.arbitration(frame.id(), true);
+                encoder.classic_control(frame.len() as _);
+                Some(encoder.finish_classic())
+            }
+            Frame::FdData(frame) => {
+                let dlc = len_to_dlc(frame.data().len());
+                encoder.arbitration(frame.id(), false);
+                // (IDE), FDF, res, BRS
+                if let Id::Standard(_) = frame.id() {
+                    encoder.push(false);
+                }
+                encoder.push(true);
+                encoder.push(false);
+                encoder.push(frame.brs());
+                let data_phase = if frame.brs() {
+                    encoder.bits.len()
+                } else {
+                    usize::MAX
+                };
+                encoder.push(frame.esi());
+                encoder.field(dlc as _, 4);
+                encoder.data(frame.data());
+                let len = dlc_to_len(dlc) as usize;
+                encoder.data(&[0; sys::CANFD_MAX_DLEN as _][frame.data().len()..len]);
+                Some(encoder.finish_fd(data_phase, len > 16))
+            }
+            Frame::Error(_) => None,
+        }
+    }
+
+    /// `false` is dominant and `true` is recessive.
+    pub fn bits(&self) -> &[bool] {
+        &self.bits
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.is_empty()
+    }
+
+    /// Number of stuff bits, including the fixed stuff bits of CAN FD frames.
+    pub fn stuff_bits(&self) -> usize {
+        self.stuff_bits
+    }
+
+    /// Bits transmitted with the nominal bitrate.
+    /// For CAN FD frames with BRS, the BRS bit and the CRC delimiter are counted here.
+    pub fn nominal_bits(&self) -> usize {
+        self.bits.len() - self.data_bits()
+    }
+
+    /// Bits transmitted with the data bitrate, i.e. from ESI to the end of the CRC sequence
+    /// of CAN FD frames with BRS. Zero for other frames.
+    pub fn data_bits(&self) -> usize {
+        self.data_phase.len()
+    }
+
+    /// Returns the transmission time with the given bitrates in bit/s.
+    /// `data_bitrate` is ignored if there are no bits in the data phase.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a bitrate in use is zero.
+    pub fn duration(&self, nominal_bitrate: u32, data_bitrate: u32) -> Duration {
+        let mut nanos = self.nominal_bits() as u64 * 1_000_000_000 / nominal_bitrate as u64;
+        if self.data_bits() > 0 {
+            nanos += self.data_bits() as u64 * 1_000_000_000 / data_bitrate as u64;
+        }
+        Duration::from_nanos(nanos)
+    }
+}
+
+#[derive(Default)]
+struct Encoder {
+    bits: Vec<bool>,
+    // bits without stuff bits, for CRC15
+    raw: Vec<bool>,
+    run: (bool, usize),
+    stuff_bits: usize,
+}
+
+impl Encoder {
+    fn push(&mut self, bit: bool) {
+        if self.run.1 == 5 {
+            self.bits.push(!self.run.0);
+            self.stuff_bits += 1;
+            self.run = (!self.run.0, 1);
+        }
+        self.bits.push(bit);
+        self.raw.push(bit);
+        if self.run.1 > 0 && self.run.0 == bit {
+            self.run.1 += 1;
+        } else {
+            self.run = (bit, 1);
+        }
+    }
+
+    fn field(&mut self, value: u32, width: u32) {
+        for i in (0..width).rev() {
+            self.push(value >> i & 1 != 0);
+        }
+    }
+
+    fn data(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.field(byte as _, 8);
+        }
+    }
+
+    // SOF, identifier, SRR, IDE and RTR/RRS
+    fn arbitration(&mut self, id: Id, rtr: bool) {
+        self.push(false);
+        match id {
+            Id::Standard(id) => {
+                self.field(id, 11);
+                self.push(rtr);
+            }
+            Id::Extended(id) => {
+                self.field(id >> 18, 11);
+                self.push(true);
+                self.push(true);
+                self.field(id, 18);
+                self.push(rtr);
+            }
+        }
+    }
+
+    // IDE and r0 (standard) or r1 and r0 (extended), and DLC
+    fn classic_control(&mut self, len: usize) {
+        self.push(false);
+        self.push(false);
+        self.field(len as _, 4);
+    }
+
+    fn finish_classic(mut self) -> Bitstream {
+        let crc = crc(self.raw.iter().copied(), CRC15);
+        self.field(crc, 15);
+        // a pending stuff bit after the CRC sequence
+        if self.run.1 == 5 {
+            self.bits.push(!self.run.0);
+            self.stuff_bits += 1;
+        }
+        self.finish(usize::MAX..usize::MAX)
+    }
+
+    fn finish_fd(mut self, data_phase: usize, crc21: bool) -> Bitstream {
+        let (poly, width, init) = if crc21 { CRC21 } else { CRC17 };
+        let stuff_count = GRAY[self.stuff_bits % 8];
+        let stuff_count = stuff_count << 1 | (stuff_count.count_ones() % 2);
+        let crc = crc(
+            self.bits
+                .iter()
+                .copied()
+                .chain((0..4).rev().map(|i| stuff_count >> i & 1 != 0)),
+            (poly, width, init),
+        );
+        let tail = (0..4)
+            .rev()
+            .map(|i| stuff_count >> i & 1 != 0)
+            .chain((0..width).rev().map(|i| crc >> i & 1 != 0))
+            .collect::<Vec<_>>();
+        for (i, &bit) in tail.iter().enumerate() {
+            if i % 4 == 0 {
+                let last = *self.bits.last().unwrap();
+                self.bits.push(!last);
+                self.stuff_bits += 1;
+            }
+            self.bits.push(bit);
+        }
+        let end = self.bits.len();
+        self.finish(data_phase.min(end)..end)
+    }
+
+    // CRC delimiter, ACK slot, ACK delimiter and EOF
+    fn finish(mut self, data_phase: Range<usize>) -> Bitstream {
+        self.bits.push(true);
+        self.bits.push(false);
+        self.bits.push(true);
+        self.bits.extend([true; 7]);
+        Bitstream {
+            bits: self.bits,
+            data_phase,
+            stuff_bits: self.stuff_bits,
+        }
+    }
+}
+
+const GRAY: [u32; 8] = [0b000, 0b001, 0b011, 0b010, 0b110, 0b111, 0b101, 0b100];
+
+fn crc<I>(bits: I, (poly, width, init): (u32, u32, u32)) -> u32
+where
+    I: IntoIterator<Item = bool>,
+{
+    let mask = (1 << width) - 1;
+    bits.into_iter().fold(init, |crc, bit| {
+        let msb = crc >> (width - 1) & 1 != 0;
+        let crc = crc << 1 & mask;
+        if msb ^ bit {
+            crc ^ poly
+        } else {
+            crc
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests;
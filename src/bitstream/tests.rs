@@ -0,0 +1,176 @@
+use super::{crc, Bitstream, CRC15, CRC17, CRC21};
+use crate::{DataFrame, ErrorFrame, FdDataFrame, Frame, Id, RemoteFrame};
+use std::time::Duration;
+
+// removes stuff bits, checking that they are placed correctly
+fn destuff(bits: &[bool]) -> Vec<bool> {
+    let mut raw = Vec::new();
+    let mut run = (false, 0);
+    for &bit in bits {
+        if run.1 == 5 {
+            assert_ne!(bit, run.0);
+            run = (bit, 1);
+            continue;
+        }
+        raw.push(bit);
+        run = if run.1 > 0 && run.0 == bit {
+            (bit, run.1 + 1)
+        } else {
+            (bit, 1)
+        };
+    }
+    raw
+}
+
+fn check_tail(bitstream: &Bitstream) {
+    assert_eq!(
+        &bitstream.bits()[bitstream.len() - 10..],
+        &[true, false, true, true, true, true, true, true, true, true]
+    );
+}
+
+fn check_classic(frame: Frame, len: usize) -> Bitstream {
+    let bitstream = Bitstream::new(&frame).unwrap();
+    check_tail(&bitstream);
+    let raw = destuff(&bitstream.bits()[..bitstream.len() - 10]);
+    assert_eq!(raw.len(), len - 10);
+    assert_eq!(bitstream.len(), len + bitstream.stuff_bits());
+    assert_eq!(crc(raw, CRC15), 0);
+    assert_eq!(bitstream.data_bits(), 0);
+    bitstream
+}
+
+#[test]
+fn test_data_standard() {
+    for len in 0..=8 {
+        let data = rand::random::<[_; 8]>();
+        let frame = Frame::Data(DataFrame::new(Id::Standard(0x42), &data[..len]));
+        check_classic(frame, 44 + len * 8);
+    }
+}
+
+#[test]
+fn test_data_extended() {
+    for len in 0..=8 {
+        let data = rand::random::<[_; 8]>();
+        let frame = Frame::Data(DataFrame::new(Id::Extended(0x1234_5678), &data[..len]));
+        check_classic(frame, 64 + len * 8);
+    }
+}
+
+#[test]
+fn test_remote() {
+    let frame = Frame::Remote(RemoteFrame::new(Id::Standard(0x42), 8));
+    let bitstream = check_classic(frame, 44);
+    // RTR
+    assert!(destuff(&bitstream.bits()[..bitstream.len() - 10])[12]);
+}
+
+#[test]
+fn test_stuffing() {
+    // SOF, ID and RTR are dominant
+    let frame = Frame::Data(DataFrame::new(Id::Standard(0), &[0; 8]));
+    let bitstream = check_classic(frame, 108);
+    assert_eq!(
+        &bitstream.bits()[..14],
+        &[
+            false, false, false, false, false, true, false, false, false, false, false, true,
+            false, false
+        ]
+    );
+    assert!(bitstream.stuff_bits() >= 64 / 5);
+}
+
+#[test]
+fn test_fd_data() {
+    for &len in &[0, 1, 8, 12, 16, 20, 24, 32, 48, 64] {
+        for &id in &[Id::Standard(0x42), Id::Extended(0x1234_5678)] {
+            let data = (0..64).map(|_| rand::random()).collect::<Vec<u8>>();
+            let frame = Frame::FdData(FdDataFrame::new(id, false, true, &data[..len]));
+            let bitstream = Bitstream::new(&frame).unwrap();
+            check_tail(&bitstream);
+
+            let header = match id {
+                Id::Standard(_) => 22,
+                Id::Extended(_) => 41,
+            };
+            let (crc_len, fixed_stuff_bits, params) = if len > 16 {
+                (21, 7, CRC21)
+            } else {
+                (17, 6, CRC17)
+            };
+            let dynamic = bitstream.len() - 10 - (4 + crc_len + fixed_stuff_bits);
+            let raw = destuff(&bitstream.bits()[..dynamic]);
+            assert_eq!(raw.len(), header + len * 8);
+            let stuff_bits = dynamic - raw.len();
+            assert_eq!(bitstream.stuff_bits(), stuff_bits + fixed_stuff_bits);
+
+            // fixed stuff bits
+            let mut last = bitstream.bits()[dynamic - 1];
+            let mut tail = Vec::new();
+            for bits in bitstream.bits()[dynamic..bitstream.len() - 10].chunks(5) {
+                assert_ne!(bits[0], last);
+                tail.extend(&bits[1..]);
+                last = *bits.last().unwrap();
+            }
+            assert_eq!(tail.len(), 4 + crc_len);
+
+            // stuff count
+            let gray = tail[..3]
+                .iter()
+                .fold(0, |acc, &bit| acc << 1 | bit as usize);
+            assert_eq!(super::GRAY[stuff_bits % 8], gray as u32);
+            assert_eq!(tail[..4].iter().filter(|&&bit| bit).count() % 2, 0);
+
+            let bits = bitstream.bits()[..dynamic].iter().chain(&tail).copied();
+            assert_eq!(crc(bits, params), 0);
+        }
+    }
+}
+
+#[test]
+fn test_fd_data_brs() {
+    let frame = Frame::FdData(FdDataFrame::new(Id::Standard(0x42), false, false, &[0; 64]));
+    let bitstream = Bitstream::new(&frame).unwrap();
+    assert_eq!(bitstream.data_bits(), 0);
+    assert_eq!(bitstream.nominal_bits(), bitstream.len());
+
+    let frame = Frame::FdData(FdDataFrame::new(Id::Standard(0x42), true, false, &[0; 64]));
+    let bitstream_brs = Bitstream::new(&frame).unwrap();
+    assert_eq!(bitstream_brs.len(), bitstream.len());
+    // SOF, ID, RRS, IDE, FDF, res, BRS and stuff bits, CRC delimiter, ACK, EOF
+    assert_eq!(bitstream_brs.nominal_bits(), 17 + 1 + 10);
+    assert_eq!(
+        bitstream_brs.duration(500_000, 2_000_000),
+        Duration::from_nanos(28 * 2000 + (bitstream.len() as u64 - 28) * 500)
+    );
+}
+
+#[test]
+fn test_duration() {
+    let frame = Frame::Data(DataFrame::new(Id::Standard(0x42), &[0x55; 8]));
+    let bitstream = Bitstream::new(&frame).unwrap();
+    assert_eq!(
+        bitstream.duration(500_000, 0),
+        Duration::from_micros(2 * bitstream.len() as u64)
+    );
+}
+
+#[test]
+fn test_error() {
+    let frame = Frame::Error(ErrorFrame(unsafe { std::mem::zeroed() }));
+    assert!(Bitstream::new(&frame).is_none());
+}
+
+#[test]
+fn test_crc() {
+    // check values of CRC-15/CAN, CRC-17/CAN-FD and CRC-21/CAN-FD (initialized with zero)
+    let bits = || {
+        b"123456789"
+            .iter()
+            .flat_map(|&byte| (0..8).rev().map(move |i| byte >> i & 1 != 0))
+    };
+    assert_eq!(crc(bits(), CRC15), 0x059e);
+    assert_eq!(crc(bits(), (CRC17.0, CRC17.1, 0)), 0x0_4f03);
+    assert_eq!(crc(bits(), (CRC21.0, CRC21.1, 0)), 0x0e_d841);
+}
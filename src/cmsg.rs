@@ -1,19 +1,43 @@
+use crate::sys;
 use std::mem::{align_of, size_of};
+use std::os::raw::c_int;
+
+const SOL_CAN_J1939: c_int = sys::SOL_CAN_J1939 as _;
+const SCM_J1939_DEST_ADDR: c_int = sys::SCM_J1939_DEST_ADDR as _;
+const SCM_J1939_DEST_NAME: c_int = sys::SCM_J1939_DEST_NAME as _;
+const SCM_J1939_PRIO: c_int = sys::SCM_J1939_PRIO as _;
+const SCM_J1939_ERRQUEUE: c_int = sys::SCM_J1939_ERRQUEUE as _;
 
 #[non_exhaustive]
 pub enum Cmsg<'a> {
     Timestamping(&'a [libc::timespec; 3]),
+    RxqOvfl(&'a u32),
+    J1939DestAddr(&'a u8),
+    J1939DestName(&'a u64),
+    J1939Prio(&'a u8),
+    J1939Errqueue(&'a libc::sock_extended_err),
     #[doc(hidden)]
     Other(&'a libc::cmsghdr),
 }
 
 impl<'a> Cmsg<'a> {
     pub fn space() -> usize {
-        [size_of::<[libc::timespec; 3]>()]
+        [size_of::<[libc::timespec; 3]>(), size_of::<u32>()]
             .iter()
             .map(|&size| unsafe { libc::CMSG_SPACE(size as _) })
-            .max()
-            .unwrap_or_default() as _
+            .sum::<u32>() as _
+    }
+}
+
+// large enough to hold all kinds of control messages in `Cmsg`,
+// including those of J1939 sockets, which are not counted in `space`
+#[repr(C, align(8))]
+pub(crate) struct CmsgBuf(pub(crate) [u8; 256]);
+
+impl CmsgBuf {
+    pub(crate) fn new() -> Self {
+        debug_assert!(Cmsg::space() <= 256);
+        Self([0; 256])
     }
 }
 
@@ -45,6 +69,14 @@ impl<'a> Iterator for CmsgIter<'a> {
             (libc::SOL_SOCKET, libc::SCM_TIMESTAMPING) => {
                 Cmsg::Timestamping(unsafe { cmsg_data(cmsg) })
             }
+            (libc::SOL_SOCKET, libc::SO_RXQ_OVFL) => Cmsg::RxqOvfl(unsafe { cmsg_data(cmsg) }),
+            (SOL_CAN_J1939, SCM_J1939_DEST_ADDR) => Cmsg::J1939DestAddr(unsafe { cmsg_data(cmsg) }),
+            (SOL_CAN_J1939, SCM_J1939_DEST_NAME) => Cmsg::J1939DestName(unsafe { cmsg_data(cmsg) }),
+            (SOL_CAN_J1939, SCM_J1939_PRIO) => Cmsg::J1939Prio(unsafe { cmsg_data(cmsg) }),
+            // followed by the address of the offender
+            (SOL_CAN_J1939, SCM_J1939_ERRQUEUE) => {
+                Cmsg::J1939Errqueue(unsafe { cmsg_data_prefix(cmsg) })
+            }
             _ => Cmsg::Other(cmsg),
         })
     }
@@ -56,3 +88,10 @@ unsafe fn cmsg_data<T>(cmsg: &libc::cmsghdr) -> &T {
     assert_eq!(data.align_offset(align_of::<T>()), 0);
     &*(data as *const T)
 }
+
+unsafe fn cmsg_data_prefix<T>(cmsg: &libc::cmsghdr) -> &T {
+    assert!(cmsg.cmsg_len >= libc::CMSG_LEN(size_of::<T>() as _) as _);
+    let data = libc::CMSG_DATA(cmsg);
+    assert_eq!(data.align_offset(align_of::<T>()), 0);
+    &*(data as *const T)
+}
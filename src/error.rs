@@ -0,0 +1,145 @@
+use std::fmt;
+use std::io;
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Errors of this crate.
+///
+/// Converts to and from [`io::Error`], so `?` works in functions returning [`io::Result`].
+/// Converting back from an [`io::Error`] made from this type restores the original variant.
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum Error {
+    /// No interface with the name exists.
+    InterfaceNotFound {
+        ifname: String,
+    },
+    /// The kernel does not support the protocol, e.g. the module (`can-raw`) is not loaded.
+    ProtocolNotSupported {
+        protocol: &'static str,
+    },
+    /// The interface cannot carry the frame because of its MTU.
+    MtuMismatch {
+        mtu: usize,
+        required: usize,
+    },
+    /// CAN FD frames are not enabled on the socket (see `set_fd_frames`).
+    FdFramesDisabled,
+    /// Control messages did not fit in the buffer and were truncated.
+    TruncatedControlData,
+    /// The frame violates the limits of its kind.
+    InvalidFrame {
+        reason: &'static str,
+    },
+    /// No bit timing satisfies the limits of the controller.
+    InvalidBitTiming {
+        reason: &'static str,
+    },
+    /// The controller does not support the link configuration.
+    InvalidLinkConfig {
+        reason: &'static str,
+    },
+    /// The kernel rejected a netlink request,
+    /// with the message of the extended acknowledgement if any.
+    Netlink {
+        errno: i32,
+        message: Option<String>,
+    },
+    Io(io::Error),
+}
+
+impl Error {
+    pub(crate) fn last_os_error() -> Self {
+        io::Error::last_os_error().into()
+    }
+
+    pub(crate) fn raw_os_error(&self) -> Option<i32> {
+        match self {
+            Self::Netlink { errno, .. } => Some(*errno),
+            Self::Io(e) => e.raw_os_error(),
+            _ => None,
+        }
+    }
+
+    pub fn kind(&self) -> io::ErrorKind {
+        match self {
+            Self::InterfaceNotFound { .. } => io::ErrorKind::NotFound,
+            Self::ProtocolNotSupported { .. } => io::ErrorKind::Unsupported,
+            Self::MtuMismatch { .. }
+            | Self::FdFramesDisabled
+            | Self::InvalidFrame { .. }
+            | Self::InvalidBitTiming { .. }
+            | Self::InvalidLinkConfig { .. } => io::ErrorKind::InvalidInput,
+            Self::TruncatedControlData => io::ErrorKind::InvalidData,
+            Self::Netlink { errno, .. } => io::Error::from_raw_os_error(*errno).kind(),
+            Self::Io(e) => e.kind(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InterfaceNotFound { ifname } => write!(fmt, "interface {:?} not found", ifname),
+            Self::ProtocolNotSupported { protocol } => write!(
+                fmt,
+                "protocol {} is not supported (is the kernel module loaded?)",
+                protocol
+            ),
+            Self::MtuMismatch { mtu, required } => write!(
+                fmt,
+                "interface MTU {} is too small for the frame (requires {})",
+                mtu, required
+            ),
+            Self::FdFramesDisabled => write!(
+                fmt,
+                "CAN FD frames are not enabled on this socket (see `set_fd_frames`)"
+            ),
+            Self::TruncatedControlData => write!(fmt, "control messages were truncated"),
+            Self::InvalidFrame { reason } => write!(fmt, "invalid frame: {}", reason),
+            Self::InvalidBitTiming { reason } => write!(fmt, "invalid bit timing: {}", reason),
+            Self::InvalidLinkConfig { reason } => {
+                write!(fmt, "invalid link configuration: {}", reason)
+            }
+            Self::Netlink {
+                errno,
+                message: Some(message),
+            } => write!(
+                fmt,
+                "{} ({})",
+                message,
+                io::Error::from_raw_os_error(*errno)
+            ),
+            Self::Netlink {
+                errno,
+                message: None,
+            } => io::Error::from_raw_os_error(*errno).fmt(fmt),
+            Self::Io(e) => e.fmt(fmt),
+        }
+    }
+}
+
+// no source, as Io displays the io::Error itself, which error reports would repeat
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        if e.get_ref().map(|e| e.is::<Self>()).unwrap_or(false) {
+            *e.into_inner().unwrap().downcast().unwrap()
+        } else {
+            Self::Io(e)
+        }
+    }
+}
+
+impl From<Error> for io::Error {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Io(e) => e,
+            e => io::Error::new(e.kind(), e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
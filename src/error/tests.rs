@@ -0,0 +1,77 @@
+use super::Error;
+use std::io;
+
+#[test]
+fn test_io_roundtrip() {
+    let e = io::Error::from(Error::InterfaceNotFound {
+        ifname: "vcan42".to_owned(),
+    });
+    assert_eq!(e.kind(), io::ErrorKind::NotFound);
+    assert!(e.to_string().contains("vcan42"));
+    match Error::from(e) {
+        Error::InterfaceNotFound { ifname } => assert_eq!(ifname, "vcan42"),
+        e => panic!("{:?}", e),
+    }
+}
+
+#[test]
+fn test_io() {
+    let e = Error::from(io::Error::from_raw_os_error(libc::ENETDOWN));
+    assert!(matches!(e, Error::Io(_)));
+    assert_eq!(
+        e.to_string(),
+        io::Error::from_raw_os_error(libc::ENETDOWN).to_string()
+    );
+    // displayed transparently, so not repeated as the source
+    assert!(std::error::Error::source(&e).is_none());
+    assert_eq!(io::Error::from(e).raw_os_error(), Some(libc::ENETDOWN));
+}
+
+#[test]
+fn test_kind() {
+    assert_eq!(
+        Error::MtuMismatch {
+            mtu: 16,
+            required: 72
+        }
+        .kind(),
+        io::ErrorKind::InvalidInput
+    );
+    assert_eq!(Error::FdFramesDisabled.kind(), io::ErrorKind::InvalidInput);
+    assert_eq!(
+        Error::TruncatedControlData.kind(),
+        io::ErrorKind::InvalidData
+    );
+    assert_eq!(
+        Error::InvalidBitTiming {
+            reason: "bitrate error too high"
+        }
+        .kind(),
+        io::ErrorKind::InvalidInput
+    );
+    assert_eq!(
+        Error::InvalidLinkConfig {
+            reason: "TDC is not supported by the controller"
+        }
+        .kind(),
+        io::ErrorKind::InvalidInput
+    );
+}
+
+#[test]
+fn test_netlink() {
+    let e = Error::Netlink {
+        errno: libc::EINVAL,
+        message: Some("bitrate error".to_owned()),
+    };
+    assert_eq!(e.kind(), io::ErrorKind::InvalidInput);
+    assert!(e.to_string().starts_with("bitrate error"));
+    let e = Error::Netlink {
+        errno: libc::EOPNOTSUPP,
+        message: None,
+    };
+    assert_eq!(
+        e.to_string(),
+        io::Error::from_raw_os_error(libc::EOPNOTSUPP).to_string()
+    );
+}
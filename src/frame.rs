@@ -1,21 +1,29 @@
+mod builder;
+mod cob_id;
 mod data;
 mod error;
 mod fd_data;
 mod id;
+mod j1939_id;
 mod remote;
 
-use crate::sys;
+use crate::{sys, Error, Result};
+pub use builder::FrameBuilder;
+pub use cob_id::CobId;
 pub use data::DataFrame;
 pub use error::ErrorFrame;
 pub use fd_data::FdDataFrame;
+pub(crate) use fd_data::{dlc_to_len, len_to_dlc};
 pub use id::Id;
+pub use j1939_id::J1939Id;
 pub use remote::RemoteFrame;
+use std::cmp::Ordering;
 use std::mem::{size_of, size_of_val, MaybeUninit};
 use std::os::raw::c_void;
+use std::{ptr, slice};
 
 #[non_exhaustive]
-#[derive(Clone, Copy, Debug)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Frame {
     Data(DataFrame),
     FdData(FdDataFrame),
@@ -25,6 +33,90 @@ pub enum Frame {
 }
 
 impl Frame {
+    /// Compares frames by their priority in bus arbitration.
+    /// A frame that wins arbitration compares less than the one that loses.
+    ///
+    /// In addition to the ordering of [`Id`], a data frame wins against a remote frame
+    /// and a classic frame wins against a CAN FD frame with the same identifier.
+    /// Error frames do not take part in arbitration and compare greater than any other frame.
+    pub fn cmp_priority(&self, other: &Self) -> Ordering {
+        match (self.arbitration_field(), other.arbitration_field()) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+
+    // bits from SOF to FDF, in the order they appear on the bus
+    #[allow(clippy::type_complexity)]
+    fn arbitration_field(&self) -> Option<(u32, bool, bool, u32, bool, bool)> {
+        let (id, rtr, fdf) = match self {
+            Self::Data(frame) => (frame.id(), false, false),
+            Self::FdData(frame) => (frame.id(), false, true),
+            Self::Remote(frame) => (frame.id(), true, false),
+            Self::Error(_) => return None,
+        };
+        let (base, ide, extension) = id.arbitration_field();
+        if ide {
+            // SRR is always recessive
+            Some((base, true, ide, extension, rtr, fdf))
+        } else {
+            Some((base, rtr, ide, extension, false, fdf))
+        }
+    }
+
+    /// Returns the frame as the bytes of `struct can_frame` or `struct canfd_frame`,
+    /// exactly as it is written to the socket.
+    pub fn as_bytes(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.as_ptr() as *const u8, self.size()) }
+    }
+
+    /// Parses the bytes of `struct can_frame` or `struct canfd_frame`.
+    /// The kind of the frame is determined by the length of `bytes`, as for frames read from a socket.
+    ///
+    /// Fails with [`Error::InvalidFrame`] if the length of `bytes` matches neither of the structures,
+    /// or the content is not a valid frame (e.g. the data length matches no DLC).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut frame = MaybeUninit::<sys::canfd_frame>::zeroed();
+        let (can_id, valid_len) = if bytes.len() == size_of::<sys::can_frame>() {
+            unsafe {
+                ptr::copy_nonoverlapping(bytes.as_ptr(), frame.as_mut_ptr() as _, bytes.len());
+                let inner = &*(frame.as_ptr() as *const sys::can_frame);
+                (inner.can_id, inner.len() as u32 <= sys::CAN_MAX_DLEN)
+            }
+        } else if bytes.len() == size_of::<sys::canfd_frame>() {
+            unsafe {
+                ptr::copy_nonoverlapping(bytes.as_ptr(), frame.as_mut_ptr() as _, bytes.len());
+                let inner = &*frame.as_ptr();
+                if inner.can_id & (sys::CAN_RTR_FLAG | sys::CAN_ERR_FLAG) != 0 {
+                    return Err(Error::InvalidFrame {
+                        reason: "CAN FD frame with RTR or ERR flag",
+                    });
+                }
+                (inner.can_id, fd_data::is_dlc_len(inner.len))
+            }
+        } else {
+            return Err(Error::InvalidFrame {
+                reason: "size matches neither can_frame nor canfd_frame",
+            });
+        };
+        if !valid_len {
+            return Err(Error::InvalidFrame {
+                reason: "data length exceeds its limit or matches no DLC",
+            });
+        }
+        // error frames carry the error class in the identifier bits
+        if can_id & (sys::CAN_EFF_FLAG | sys::CAN_ERR_FLAG) == 0
+            && can_id & sys::CAN_EFF_MASK > sys::CAN_SFF_MASK
+        {
+            return Err(Error::InvalidFrame {
+                reason: "standard identifier with bits above 11 bits",
+            });
+        }
+        Ok(unsafe { Self::from_raw(frame, bytes.len()) }.unwrap())
+    }
+
     pub(crate) unsafe fn from_raw(
         frame: MaybeUninit<sys::canfd_frame>,
         size: usize,
@@ -0,0 +1,95 @@
+use super::{DataFrame, FdDataFrame, Frame, Id, RemoteFrame};
+use crate::{sys, Error, Result};
+
+/// Builds a [`Frame`], choosing its kind from the payload and flags.
+///
+/// - [`RemoteFrame`] if [`remote`](Self::remote) is set.
+/// - [`FdDataFrame`] if the data is longer than 8 bytes or any of
+///   [`fd`](Self::fd), [`brs`](Self::brs) and [`esi`](Self::esi) is set.
+/// - [`DataFrame`] otherwise.
+///
+/// ```
+/// use socketcan_alt::{Frame, FrameBuilder, Id};
+///
+/// let frame = FrameBuilder::new(Id::Standard(42)).data(&[0; 12]).build();
+/// assert!(matches!(frame, Frame::FdData(_)));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct FrameBuilder<'a> {
+    id: Id,
+    data: &'a [u8],
+    remote: Option<u8>,
+    fd: bool,
+    brs: bool,
+    esi: bool,
+}
+
+impl<'a> FrameBuilder<'a> {
+    pub fn new(id: Id) -> Self {
+        Self {
+            id,
+            data: &[],
+            remote: None,
+            fd: false,
+            brs: false,
+            esi: false,
+        }
+    }
+
+    pub fn data(mut self, data: &'a [u8]) -> Self {
+        self.data = data;
+        self
+    }
+
+    /// Requests a remote frame with the given data length.
+    pub fn remote(mut self, len: u8) -> Self {
+        self.remote = Some(len);
+        self
+    }
+
+    /// Forces a CAN FD frame even if the data fits in a classic frame.
+    pub fn fd(mut self, fd: bool) -> Self {
+        self.fd = fd;
+        self
+    }
+
+    pub fn brs(mut self, brs: bool) -> Self {
+        self.brs = brs;
+        self
+    }
+
+    pub fn esi(mut self, esi: bool) -> Self {
+        self.esi = esi;
+        self
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `id` exceeds its limit, `data` is longer than 64 bytes,
+    /// or a remote frame is requested with data, CAN FD flags or a length greater than 8.
+    pub fn build(self) -> Frame {
+        self.try_build().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Fails with [`Error::InvalidFrame`] in the cases where [`build`](Self::build) panics.
+    pub fn try_build(self) -> Result<Frame> {
+        let fd = self.fd || self.brs || self.esi || self.data.len() > sys::CAN_MAX_DLEN as _;
+        if let Some(len) = self.remote {
+            if fd || !self.data.is_empty() {
+                return Err(Error::InvalidFrame {
+                    reason: "remote frame with data or CAN FD flags",
+                });
+            }
+            Ok(Frame::Remote(RemoteFrame::try_new(self.id, len)?))
+        } else if fd {
+            Ok(Frame::FdData(FdDataFrame::try_new(
+                self.id, self.brs, self.esi, self.data,
+            )?))
+        } else {
+            Ok(Frame::Data(DataFrame::try_new(self.id, self.data)?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
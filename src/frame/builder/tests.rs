@@ -0,0 +1,67 @@
+use super::FrameBuilder;
+use crate::{Frame, Id};
+
+#[test]
+fn test_data() {
+    let data = rand::random::<[_; 8]>();
+    match FrameBuilder::new(Id::Standard(42)).data(&data).build() {
+        Frame::Data(frame) => {
+            assert_eq!(frame.id(), Id::Standard(42));
+            assert_eq!(frame.data(), &data);
+        }
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn test_fd_data_long() {
+    let data = rand::random::<[_; 12]>();
+    match FrameBuilder::new(Id::Standard(42)).data(&data).build() {
+        Frame::FdData(frame) => {
+            assert!(!frame.brs());
+            assert_eq!(frame.data(), &data);
+        }
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn test_fd_data_flags() {
+    let data = rand::random::<[_; 8]>();
+    match FrameBuilder::new(Id::Standard(42))
+        .data(&data)
+        .brs(true)
+        .build()
+    {
+        Frame::FdData(frame) => {
+            assert!(frame.brs());
+            assert!(!frame.esi());
+            assert_eq!(frame.data(), &data);
+        }
+        _ => panic!(),
+    }
+    match FrameBuilder::new(Id::Standard(42)).fd(true).build() {
+        Frame::FdData(frame) => assert!(frame.data().is_empty()),
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn test_remote() {
+    match FrameBuilder::new(Id::Extended(4242)).remote(3).build() {
+        Frame::Remote(frame) => {
+            assert_eq!(frame.id(), Id::Extended(4242));
+            assert_eq!(frame.len(), 3);
+        }
+        _ => panic!(),
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_remote_fd() {
+    FrameBuilder::new(Id::Standard(42))
+        .remote(3)
+        .fd(true)
+        .build();
+}
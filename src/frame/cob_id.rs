@@ -0,0 +1,70 @@
+use super::Id;
+
+/// View of an 11-bit identifier as CANopen COB-ID in the predefined connection set.
+///
+/// ```
+/// use socketcan_alt::{CobId, Id};
+///
+/// let id = Id::from(CobId {
+///     function: CobId::TPDO1,
+///     node: 0x10,
+/// });
+/// assert_eq!(id, Id::Standard(0x190));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CobId {
+    /// 4-bit function code
+    pub function: u8,
+    /// 7-bit node-ID
+    pub node: u8,
+}
+
+impl CobId {
+    pub const NMT: u8 = 0x0;
+    /// SYNC with node-ID 0, EMCY otherwise
+    pub const SYNC_EMCY: u8 = 0x1;
+    pub const TIME: u8 = 0x2;
+    pub const TPDO1: u8 = 0x3;
+    pub const RPDO1: u8 = 0x4;
+    pub const TPDO2: u8 = 0x5;
+    pub const RPDO2: u8 = 0x6;
+    pub const TPDO3: u8 = 0x7;
+    pub const RPDO3: u8 = 0x8;
+    pub const TPDO4: u8 = 0x9;
+    pub const RPDO4: u8 = 0xa;
+    /// SDO from server to client
+    pub const TSDO: u8 = 0xb;
+    /// SDO from client to server
+    pub const RSDO: u8 = 0xc;
+    /// NMT error control (heartbeat, node guarding and boot-up)
+    pub const NMT_ERROR_CONTROL: u8 = 0xe;
+}
+
+/// Fails if the identifier is not standard. The original identifier is returned as the error.
+impl TryFrom<Id> for CobId {
+    type Error = Id;
+
+    fn try_from(id: Id) -> Result<Self, Self::Error> {
+        match id {
+            Id::Standard(id) => Ok(Self {
+                function: (id >> 7 & 0xf) as _,
+                node: (id & 0x7f) as _,
+            }),
+            Id::Extended(_) => Err(id),
+        }
+    }
+}
+
+/// # Panics
+///
+/// Panics if `function` exceeds 4 bits or `node` exceeds 7 bits.
+impl From<CobId> for Id {
+    fn from(id: CobId) -> Self {
+        assert!(id.function <= 0xf);
+        assert!(id.node <= 0x7f);
+        Id::Standard((id.function as u32) << 7 | id.node as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,37 @@
+use super::CobId;
+use crate::Id;
+
+#[test]
+fn test_cob_id() {
+    let id = CobId::try_from(Id::Standard(0x701)).unwrap();
+    assert_eq!(
+        id,
+        CobId {
+            function: CobId::NMT_ERROR_CONTROL,
+            node: 1,
+        }
+    );
+    assert_eq!(Id::from(id), Id::Standard(0x701));
+}
+
+#[test]
+fn test_roundtrip() {
+    for id in 0..0x800 {
+        let id = Id::Standard(id);
+        assert_eq!(Id::from(CobId::try_from(id).unwrap()), id);
+    }
+}
+
+#[test]
+fn test_extended() {
+    assert_eq!(CobId::try_from(Id::Extended(42)), Err(Id::Extended(42)));
+}
+
+#[test]
+#[should_panic]
+fn test_node_exceed() {
+    let _ = Id::from(CobId {
+        function: CobId::RSDO,
+        node: 0x80,
+    });
+}
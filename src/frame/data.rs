@@ -1,6 +1,7 @@
-use super::Id;
-use crate::sys;
+use super::{FdDataFrame, Id};
+use crate::{sys, Error, Result};
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::mem::MaybeUninit;
 
 #[derive(Clone, Copy)]
@@ -11,23 +12,80 @@ impl DataFrame {
     ///
     /// Panics if `id` exceeds its limit or `data` is longer than 8 bytes.
     pub fn new(id: Id, data: &[u8]) -> Self {
-        assert!(data.len() <= sys::CAN_MAX_DLEN as _);
-        let mut inner = MaybeUninit::<sys::can_frame>::zeroed();
-        unsafe {
-            (*inner.as_mut_ptr()).can_id = id.into_can_id();
-            (&mut *inner.as_mut_ptr()).set_len(data.len() as _);
-            (*inner.as_mut_ptr()).data[..data.len()].copy_from_slice(data);
-            Self(inner.assume_init())
+        Self::try_new(id, data).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Fails with [`Error::InvalidFrame`] if `id` exceeds its limit or `data` is longer than 8 bytes.
+    pub fn try_new(id: Id, data: &[u8]) -> Result<Self> {
+        if data.len() > sys::CAN_MAX_DLEN as _ {
+            return Err(Error::InvalidFrame {
+                reason: "data is longer than 8 bytes",
+            });
         }
+        let mut frame = Self(unsafe { MaybeUninit::zeroed().assume_init() });
+        frame.0.can_id = id.try_into_can_id()?;
+        frame.set_data(data);
+        Ok(frame)
     }
 
     pub fn id(&self) -> Id {
         Id::from_can_id(self.0.can_id)
     }
 
+    /// # Panics
+    ///
+    /// Panics if `id` exceeds its limit.
+    pub fn set_id(&mut self, id: Id) {
+        self.0.can_id = id.into_can_id();
+    }
+
     pub fn data(&self) -> &[u8] {
         &self.0.data[..self.0.len() as _]
     }
+
+    pub fn data_mut(&mut self) -> &mut [u8] {
+        let len = self.0.len() as _;
+        &mut self.0.data[..len]
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `data` is longer than 8 bytes.
+    pub fn set_data(&mut self, data: &[u8]) {
+        assert!(data.len() <= sys::CAN_MAX_DLEN as _);
+        unsafe { self.0.set_len(data.len() as _) };
+        self.0.data[..data.len()].copy_from_slice(data);
+        self.0.data[data.len()..].fill(0);
+    }
+}
+
+/// Fails if the data of the CAN FD frame is longer than 8 bytes.
+/// The original frame is returned as the error.
+impl TryFrom<FdDataFrame> for DataFrame {
+    type Error = FdDataFrame;
+
+    fn try_from(frame: FdDataFrame) -> std::result::Result<Self, Self::Error> {
+        if frame.data().len() <= sys::CAN_MAX_DLEN as _ {
+            Ok(Self::new(frame.id(), frame.data()))
+        } else {
+            Err(frame)
+        }
+    }
+}
+
+impl PartialEq for DataFrame {
+    fn eq(&self, other: &Self) -> bool {
+        self.id() == other.id() && self.data() == other.data()
+    }
+}
+
+impl Eq for DataFrame {}
+
+impl Hash for DataFrame {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id().hash(state);
+        self.data().hash(state);
+    }
 }
 
 impl fmt::Debug for DataFrame {
@@ -1,11 +1,5 @@
 use super::DataFrame;
-use crate::Id;
-
-impl PartialEq for DataFrame {
-    fn eq(&self, other: &Self) -> bool {
-        self.id() == other.id() && self.data() == other.data()
-    }
-}
+use crate::{FdDataFrame, Frame, Id};
 
 #[test]
 fn test_data() {
@@ -20,3 +14,37 @@ fn test_data() {
 fn test_data_exceed() {
     DataFrame::new(Id::Standard(42), &[0; 12]);
 }
+
+#[test]
+fn test_data_mut() {
+    let mut frame = DataFrame::new(Id::Standard(42), &[0; 4]);
+    frame.data_mut()[1] = 42;
+    assert_eq!(frame.data(), &[0, 42, 0, 0]);
+    frame.set_id(Id::Extended(4242));
+    frame.set_data(&[1, 2]);
+    assert_eq!(frame.id(), Id::Extended(4242));
+    assert_eq!(frame.data(), &[1, 2]);
+}
+
+#[test]
+fn test_data_shrink() {
+    let mut frame = DataFrame::new(Id::Standard(42), &[0xff; 8]);
+    frame.set_data(&[1, 2]);
+    assert_eq!(frame.0.data, [1, 2, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(
+        Frame::Data(frame).as_bytes(),
+        Frame::Data(DataFrame::new(Id::Standard(42), &[1, 2])).as_bytes()
+    );
+}
+
+#[test]
+fn test_data_try_from_fd_data() {
+    let data = rand::random::<[_; 8]>();
+    let frame = FdDataFrame::new(Id::Standard(42), true, false, &data);
+    assert_eq!(
+        DataFrame::try_from(frame).unwrap(),
+        DataFrame::new(Id::Standard(42), &data)
+    );
+    let frame = FdDataFrame::new(Id::Standard(42), true, false, &[0; 12]);
+    assert_eq!(DataFrame::try_from(frame).unwrap_err(), frame);
+}
@@ -1,9 +1,31 @@
 use crate::sys;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 
 #[doc(hidden)]
 #[derive(Clone, Copy)]
-pub struct ErrorFrame(pub(super) sys::can_frame);
+pub struct ErrorFrame(pub(crate) sys::can_frame);
+
+impl ErrorFrame {
+    fn data(&self) -> &[u8] {
+        &self.0.data[..self.0.len() as _]
+    }
+}
+
+impl PartialEq for ErrorFrame {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.can_id == other.0.can_id && self.data() == other.data()
+    }
+}
+
+impl Eq for ErrorFrame {}
+
+impl Hash for ErrorFrame {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.can_id.hash(state);
+        self.data().hash(state);
+    }
+}
 
 impl fmt::Debug for ErrorFrame {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -1,7 +1,20 @@
 use super::ErrorFrame;
+use crate::sys;
+use std::mem::MaybeUninit;
 
-impl PartialEq for ErrorFrame {
-    fn eq(&self, _: &Self) -> bool {
-        false
+fn error_frame(class: u32, data: &[u8]) -> ErrorFrame {
+    let mut inner = MaybeUninit::<sys::can_frame>::zeroed();
+    unsafe {
+        (*inner.as_mut_ptr()).can_id = class | sys::CAN_ERR_FLAG;
+        (&mut *inner.as_mut_ptr()).set_len(data.len() as _);
+        (&mut (*inner.as_mut_ptr()).data)[..data.len()].copy_from_slice(data);
+        ErrorFrame(inner.assume_init())
     }
 }
+
+#[test]
+fn test_eq() {
+    let data = rand::random::<[_; 8]>();
+    assert_eq!(error_frame(0x40, &data), error_frame(0x40, &data));
+    assert_ne!(error_frame(0x40, &data), error_frame(0x04, &data));
+}
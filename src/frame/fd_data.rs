@@ -1,9 +1,29 @@
-use super::Id;
-use crate::sys;
+use super::{DataFrame, Id};
+use crate::{sys, Error, Result};
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::mem::MaybeUninit;
 
-const DLC: [u8; sys::CANFD_MAX_DLC as _] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 12, 16, 20, 24, 32, 48];
+// data length of each DLC
+const DLC: [u8; sys::CANFD_MAX_DLC as usize + 1] =
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 12, 16, 20, 24, 32, 48, 64];
+
+// whether a DLC encodes the data length
+pub(super) fn is_dlc_len(len: u8) -> bool {
+    DLC.contains(&len)
+}
+
+// the data length of a DLC
+pub(crate) fn dlc_to_len(dlc: u8) -> u8 {
+    DLC[(dlc & sys::CANFD_MAX_DLC as u8) as usize]
+}
+
+// the smallest DLC holding the data length, or the maximum
+pub(crate) fn len_to_dlc(len: usize) -> u8 {
+    DLC.iter()
+        .position(|&dlc_len| dlc_len as usize >= len)
+        .unwrap_or(sys::CANFD_MAX_DLC as _) as _
+}
 
 #[derive(Clone, Copy)]
 pub struct FdDataFrame(pub(super) sys::canfd_frame);
@@ -13,37 +33,104 @@ impl FdDataFrame {
     ///
     /// Panics if `id` exceeds its limit or `data` is longer than 64 bytes.
     pub fn new(id: Id, brs: bool, esi: bool, data: &[u8]) -> Self {
-        assert!(data.len() <= sys::CANFD_MAX_DLEN as _);
-        let mut inner = MaybeUninit::<sys::canfd_frame>::zeroed();
-        unsafe {
-            (*inner.as_mut_ptr()).can_id = id.into_can_id();
-            (*inner.as_mut_ptr()).len = DLC
-                .iter()
-                .copied()
-                .find(|&dlc| dlc as usize >= data.len())
-                .unwrap_or(sys::CANFD_MAX_DLEN as _);
-            (*inner.as_mut_ptr()).flags = if brs { sys::CANFD_BRS as _ } else { 0 }
-                | if esi { sys::CANFD_ESI as _ } else { 0 };
-            (*inner.as_mut_ptr()).data[..data.len()].copy_from_slice(data);
-            Self(inner.assume_init())
+        Self::try_new(id, brs, esi, data).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Fails with [`Error::InvalidFrame`] if `id` exceeds its limit or `data` is longer than 64 bytes.
+    pub fn try_new(id: Id, brs: bool, esi: bool, data: &[u8]) -> Result<Self> {
+        if data.len() > sys::CANFD_MAX_DLEN as _ {
+            return Err(Error::InvalidFrame {
+                reason: "data is longer than 64 bytes",
+            });
         }
+        let mut frame = Self(unsafe { MaybeUninit::zeroed().assume_init() });
+        frame.0.can_id = id.try_into_can_id()?;
+        frame.set_brs(brs);
+        frame.set_esi(esi);
+        frame.set_data(data);
+        Ok(frame)
     }
 
     pub fn id(&self) -> Id {
         Id::from_can_id(self.0.can_id)
     }
 
+    /// # Panics
+    ///
+    /// Panics if `id` exceeds its limit.
+    pub fn set_id(&mut self, id: Id) {
+        self.0.can_id = id.into_can_id();
+    }
+
     pub fn brs(&self) -> bool {
         self.0.flags & (sys::CANFD_BRS as u8) != 0
     }
 
+    pub fn set_brs(&mut self, brs: bool) {
+        self.set_flag(sys::CANFD_BRS as _, brs);
+    }
+
     pub fn esi(&self) -> bool {
         self.0.flags & (sys::CANFD_ESI as u8) != 0
     }
 
+    pub fn set_esi(&mut self, esi: bool) {
+        self.set_flag(sys::CANFD_ESI as _, esi);
+    }
+
+    fn set_flag(&mut self, flag: u8, value: bool) {
+        if value {
+            self.0.flags |= flag;
+        } else {
+            self.0.flags &= !flag;
+        }
+    }
+
     pub fn data(&self) -> &[u8] {
         &self.0.data[..self.0.len as _]
     }
+
+    pub fn data_mut(&mut self) -> &mut [u8] {
+        &mut self.0.data[..self.0.len as _]
+    }
+
+    /// `data` is padded with zeros to the next valid CAN FD length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data` is longer than 64 bytes.
+    pub fn set_data(&mut self, data: &[u8]) {
+        assert!(data.len() <= sys::CANFD_MAX_DLEN as _);
+        self.0.len = dlc_to_len(len_to_dlc(data.len()));
+        self.0.data[..data.len()].copy_from_slice(data);
+        self.0.data[data.len()..].fill(0);
+    }
+}
+
+impl From<DataFrame> for FdDataFrame {
+    fn from(frame: DataFrame) -> Self {
+        Self::new(frame.id(), false, false, frame.data())
+    }
+}
+
+impl PartialEq for FdDataFrame {
+    fn eq(&self, other: &Self) -> bool {
+        self.id() == other.id()
+            && self.brs() == other.brs()
+            && self.esi() == other.esi()
+            && self.data() == other.data()
+    }
+}
+
+impl Eq for FdDataFrame {}
+
+impl Hash for FdDataFrame {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id().hash(state);
+        self.brs().hash(state);
+        self.esi().hash(state);
+        self.data().hash(state);
+    }
 }
 
 impl fmt::Debug for FdDataFrame {
@@ -1,10 +1,19 @@
-use super::FdDataFrame;
-use crate::Id;
+use super::{dlc_to_len, is_dlc_len, len_to_dlc, FdDataFrame};
+use crate::{DataFrame, Id};
 
-impl PartialEq for FdDataFrame {
-    fn eq(&self, other: &Self) -> bool {
-        self.id() == other.id() && self.data() == other.data()
+#[test]
+fn test_dlc() {
+    assert_eq!(len_to_dlc(8), 8);
+    assert_eq!(len_to_dlc(9), 9);
+    assert_eq!(len_to_dlc(33), 14);
+    assert_eq!(len_to_dlc(64), 15);
+    assert_eq!(dlc_to_len(9), 12);
+    assert_eq!(dlc_to_len(15), 64);
+    for dlc in 0..=15 {
+        assert_eq!(len_to_dlc(dlc_to_len(dlc) as _), dlc);
+        assert!(is_dlc_len(dlc_to_len(dlc)));
     }
+    assert!(!is_dlc_len(9));
 }
 
 #[test]
@@ -34,3 +43,40 @@ fn test_fd_data_padded() {
 fn test_fd_data_exceed() {
     FdDataFrame::new(Id::Standard(0x42), false, false, &[0; 72]);
 }
+
+#[test]
+fn test_fd_data_eq() {
+    let data = rand::random::<[_; 8]>();
+    assert_eq!(
+        FdDataFrame::new(Id::Standard(42), true, false, &data),
+        FdDataFrame::new(Id::Standard(42), true, false, &data)
+    );
+    assert_ne!(
+        FdDataFrame::new(Id::Standard(42), true, false, &data),
+        FdDataFrame::new(Id::Standard(42), false, false, &data)
+    );
+}
+
+#[test]
+fn test_fd_data_mut() {
+    let mut frame = FdDataFrame::new(Id::Standard(42), false, false, &[0xff; 20]);
+    frame.data_mut()[1] = 42;
+    assert_eq!(frame.data()[..2], [0xff, 42]);
+    frame.set_brs(true);
+    frame.set_esi(true);
+    frame.set_esi(false);
+    frame.set_data(&[1; 9]);
+    assert!(frame.brs());
+    assert!(!frame.esi());
+    assert_eq!(frame.data(), &[1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0]);
+    assert!(frame.0.data[12..].iter().all(|&b| b == 0));
+}
+
+#[test]
+fn test_fd_data_from_data() {
+    let data = rand::random::<[_; 8]>();
+    assert_eq!(
+        FdDataFrame::from(DataFrame::new(Id::Extended(4242), &data)),
+        FdDataFrame::new(Id::Extended(4242), false, false, &data)
+    );
+}
@@ -1,7 +1,10 @@
-use crate::sys;
+use crate::{sys, Error, Result};
+use std::cmp::Ordering;
 
-#[derive(Clone, Copy, Debug)]
-#[cfg_attr(test, derive(PartialEq))]
+/// Identifiers are ordered by their priority in bus arbitration:
+/// an identifier that wins arbitration compares less than the one that loses.
+/// A standard identifier wins against an extended identifier with the same base (upper 11 bits).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Id {
     /// 11-bit identifier
     Standard(u32),
@@ -18,19 +21,41 @@ impl Id {
         }
     }
 
+    pub(crate) fn try_into_can_id(self) -> Result<u32> {
+        match self {
+            Self::Standard(id) if id <= sys::CAN_SFF_MASK => Ok(id),
+            Self::Extended(id) if id <= sys::CAN_EFF_MASK => Ok(id | sys::CAN_EFF_FLAG),
+            _ => Err(Error::InvalidFrame {
+                reason: "identifier exceeds its limit",
+            }),
+        }
+    }
+
     pub(crate) fn into_can_id(self) -> u32 {
+        self.try_into_can_id().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    // (base identifier, IDE bit, identifier extension) in the order they appear on the bus
+    pub(super) fn arbitration_field(self) -> (u32, bool, u32) {
+        const EXTENSION_BITS: u32 = sys::CAN_EFF_ID_BITS - sys::CAN_SFF_ID_BITS;
         match self {
-            Self::Standard(id) => {
-                assert!(id <= sys::CAN_SFF_MASK);
-                id
-            }
-            Self::Extended(id) => {
-                assert!(id <= sys::CAN_EFF_MASK);
-                id | sys::CAN_EFF_FLAG
-            }
+            Self::Standard(id) => (id, false, 0),
+            Self::Extended(id) => (id >> EXTENSION_BITS, true, id & ((1 << EXTENSION_BITS) - 1)),
         }
     }
 }
 
+impl PartialOrd for Id {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Id {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.arbitration_field().cmp(&other.arbitration_field())
+    }
+}
+
 #[cfg(test)]
 mod tests;
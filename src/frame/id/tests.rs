@@ -22,3 +22,32 @@ fn test_extended() {
 fn test_extended_exceed() {
     Id::Extended(0x2000_0000).into_can_id();
 }
+
+#[test]
+fn test_ord() {
+    assert!(Id::Standard(0x100) < Id::Standard(0x101));
+    assert!(Id::Extended(0x100 << 18) < Id::Extended((0x100 << 18) | 1));
+    assert!(Id::Standard(0x100) < Id::Extended(0x100 << 18));
+    assert!(Id::Extended(0x0ff << 18 | 0x3ffff) < Id::Standard(0x100));
+    assert!(Id::Standard(0x7ff) > Id::Extended(0x1fff_ffff >> 1));
+}
+
+#[test]
+fn test_ord_eq() {
+    let mut ids = vec![
+        Id::Extended(0x100 << 18),
+        Id::Standard(0x100),
+        Id::Extended(42),
+        Id::Standard(42),
+    ];
+    ids.sort();
+    assert_eq!(
+        ids,
+        [
+            Id::Extended(42),
+            Id::Standard(42),
+            Id::Standard(0x100),
+            Id::Extended(0x100 << 18),
+        ]
+    );
+}
@@ -0,0 +1,100 @@
+use super::Id;
+
+/// View of a 29-bit identifier as SAE J1939.
+///
+/// ```
+/// use socketcan_alt::{Id, J1939Id};
+///
+/// // PGN 59904 (Request), PDU1 addressed to 0x42
+/// let id = Id::from(J1939Id {
+///     priority: 6,
+///     pgn: 0xea00,
+///     destination: Some(0x42),
+///     source: 0x80,
+/// });
+/// assert_eq!(id, Id::Extended(0x18ea_4280));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct J1939Id {
+    /// 3-bit priority
+    pub priority: u8,
+    /// 18-bit parameter group number.
+    /// For PDU1 format, the PDU specific field (lower 8 bits) is zero.
+    pub pgn: u32,
+    /// Destination address for PDU1 format. `None` for PDU2 format.
+    pub destination: Option<u8>,
+    /// Source address
+    pub source: u8,
+}
+
+impl J1939Id {
+    /// The PDU format field, bits 8 to 15 of `pgn`.
+    ///
+    /// Derived from `pgn` rather than stored, so that the two cannot disagree.
+    pub fn pdu_format(&self) -> u8 {
+        (self.pgn >> 8) as _
+    }
+
+    /// PDU1 format is addressed to a destination, PDU2 format is broadcast.
+    pub fn is_pdu1(&self) -> bool {
+        is_pdu1(self.pdu_format())
+    }
+}
+
+fn is_pdu1(pdu_format: u8) -> bool {
+    pdu_format < 240
+}
+
+/// Fails if the identifier is not extended. The original identifier is returned as the error.
+impl TryFrom<Id> for J1939Id {
+    type Error = Id;
+
+    fn try_from(id: Id) -> Result<Self, Self::Error> {
+        match id {
+            Id::Extended(id) => {
+                let pgn = id >> 8 & 0x3_ffff;
+                let (pgn, destination) = if is_pdu1((pgn >> 8) as _) {
+                    (pgn & !0xff, Some(pgn as _))
+                } else {
+                    (pgn, None)
+                };
+                Ok(Self {
+                    priority: (id >> 26 & 0x7) as _,
+                    pgn,
+                    destination,
+                    source: id as _,
+                })
+            }
+            Id::Standard(_) => Err(id),
+        }
+    }
+}
+
+/// # Panics
+///
+/// Panics if `priority` exceeds 3 bits or `pgn` exceeds 18 bits,
+/// or `destination` does not match the PDU format of `pgn`
+/// (`Some` with a PDU1 PGN whose lower 8 bits are zero, `None` with a PDU2 PGN).
+impl From<J1939Id> for Id {
+    fn from(id: J1939Id) -> Self {
+        assert!(id.priority <= 0x7);
+        assert!(id.pgn <= 0x3_ffff);
+        let pdu_specific = match (id.is_pdu1(), id.destination) {
+            (true, Some(destination)) => {
+                assert_eq!(id.pgn & 0xff, 0);
+                destination
+            }
+            (false, None) => id.pgn as _,
+            _ => panic!("destination does not match PDU format"),
+        };
+        Id::Extended(
+            (id.priority as u32) << 26
+                | (id.pgn & !0xff) << 8
+                | (pdu_specific as u32) << 8
+                | id.source as u32,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests;
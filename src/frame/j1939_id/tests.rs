@@ -0,0 +1,83 @@
+use super::J1939Id;
+use crate::Id;
+
+#[test]
+fn test_pdu1() {
+    let id = J1939Id::try_from(Id::Extended(0x18ea_4280)).unwrap();
+    assert_eq!(
+        id,
+        J1939Id {
+            priority: 6,
+            pgn: 0xea00,
+            destination: Some(0x42),
+            source: 0x80,
+        }
+    );
+    assert_eq!(id.pdu_format(), 0xea);
+    assert!(id.is_pdu1());
+    assert_eq!(Id::from(id), Id::Extended(0x18ea_4280));
+}
+
+#[test]
+fn test_pdu2() {
+    // EEC1 with data page
+    let id = J1939Id::try_from(Id::Extended(0x0df0_0400)).unwrap();
+    assert_eq!(
+        id,
+        J1939Id {
+            priority: 3,
+            pgn: 0x1_f004,
+            destination: None,
+            source: 0x00,
+        }
+    );
+    assert_eq!(id.pdu_format(), 0xf0);
+    assert!(!id.is_pdu1());
+    assert_eq!(Id::from(id), Id::Extended(0x0df0_0400));
+}
+
+#[test]
+fn test_roundtrip() {
+    for _ in 0..1000 {
+        let id = Id::Extended(rand::random::<u32>() & 0x1fff_ffff);
+        assert_eq!(Id::from(J1939Id::try_from(id).unwrap()), id);
+    }
+}
+
+#[test]
+fn test_standard() {
+    assert_eq!(J1939Id::try_from(Id::Standard(42)), Err(Id::Standard(42)));
+}
+
+#[test]
+#[should_panic]
+fn test_pdu1_without_destination() {
+    let _ = Id::from(J1939Id {
+        priority: 6,
+        pgn: 0xea00,
+        destination: None,
+        source: 0x80,
+    });
+}
+
+#[test]
+#[should_panic]
+fn test_pdu2_with_destination() {
+    let _ = Id::from(J1939Id {
+        priority: 6,
+        pgn: 0xf004,
+        destination: Some(0x42),
+        source: 0x80,
+    });
+}
+
+#[test]
+#[should_panic]
+fn test_priority_exceed() {
+    let _ = Id::from(J1939Id {
+        priority: 8,
+        pgn: 0xf004,
+        destination: None,
+        source: 0x80,
+    });
+}
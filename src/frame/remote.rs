@@ -1,6 +1,7 @@
 use super::Id;
-use crate::sys;
+use crate::{sys, Error, Result};
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::mem::MaybeUninit;
 
 #[derive(Clone, Copy)]
@@ -11,22 +12,59 @@ impl RemoteFrame {
     ///
     /// Panics if `id` exceeds its limit or `len` is greater than 8.
     pub fn new(id: Id, len: u8) -> Self {
-        assert!(len <= sys::CAN_MAX_DLEN as _);
-        let mut inner = MaybeUninit::<sys::can_frame>::zeroed();
-        unsafe {
-            (*inner.as_mut_ptr()).can_id = id.into_can_id();
-            (&mut *inner.as_mut_ptr()).set_len(len as _);
-            Self(inner.assume_init())
+        Self::try_new(id, len).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Fails with [`Error::InvalidFrame`] if `id` exceeds its limit or `len` is greater than 8.
+    pub fn try_new(id: Id, len: u8) -> Result<Self> {
+        if len > sys::CAN_MAX_DLEN as _ {
+            return Err(Error::InvalidFrame {
+                reason: "length is greater than 8",
+            });
         }
+        let mut frame = Self(unsafe { MaybeUninit::zeroed().assume_init() });
+        frame.0.can_id = id.try_into_can_id()? | sys::CAN_RTR_FLAG;
+        frame.set_len(len);
+        Ok(frame)
     }
 
     pub fn id(&self) -> Id {
         Id::from_can_id(self.0.can_id)
     }
 
+    /// # Panics
+    ///
+    /// Panics if `id` exceeds its limit.
+    pub fn set_id(&mut self, id: Id) {
+        self.0.can_id = id.into_can_id() | sys::CAN_RTR_FLAG;
+    }
+
     pub fn len(&self) -> u8 {
         self.0.len()
     }
+
+    /// # Panics
+    ///
+    /// Panics if `len` is greater than 8.
+    pub fn set_len(&mut self, len: u8) {
+        assert!(len <= sys::CAN_MAX_DLEN as _);
+        unsafe { self.0.set_len(len) };
+    }
+}
+
+impl PartialEq for RemoteFrame {
+    fn eq(&self, other: &Self) -> bool {
+        self.id() == other.id() && self.len() == other.len()
+    }
+}
+
+impl Eq for RemoteFrame {}
+
+impl Hash for RemoteFrame {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id().hash(state);
+        self.len().hash(state);
+    }
 }
 
 impl fmt::Debug for RemoteFrame {
@@ -1,11 +1,5 @@
 use super::RemoteFrame;
-use crate::Id;
-
-impl PartialEq for RemoteFrame {
-    fn eq(&self, other: &Self) -> bool {
-        self.id() == other.id() && self.len() == other.len()
-    }
-}
+use crate::{sys, Frame, Id};
 
 #[test]
 fn test_remote() {
@@ -19,3 +13,23 @@ fn test_remote() {
 fn test_remote_exceed() {
     RemoteFrame::new(Id::Standard(42), 12);
 }
+
+#[test]
+fn test_remote_set() {
+    let mut frame = RemoteFrame::new(Id::Standard(42), 3);
+    frame.set_id(Id::Extended(4242));
+    frame.set_len(8);
+    assert_eq!(frame.id(), Id::Extended(4242));
+    assert_eq!(frame.len(), 8);
+    assert_ne!(frame.0.can_id & sys::CAN_RTR_FLAG, 0);
+}
+
+#[test]
+fn test_remote_rtr_flag() {
+    let frame = RemoteFrame::new(Id::Standard(42), 3);
+    assert_ne!(frame.0.can_id & sys::CAN_RTR_FLAG, 0);
+    match Frame::from_bytes(Frame::Remote(frame).as_bytes()) {
+        Ok(Frame::Remote(frame)) => assert_eq!(frame.id(), Id::Standard(42)),
+        _ => panic!(),
+    }
+}
@@ -1,5 +1,7 @@
 use super::Frame;
-use crate::{sys, Id};
+use crate::{sys, DataFrame, FdDataFrame, Id, RemoteFrame};
+use rand::Rng;
+use std::collections::HashSet;
 use std::mem::{align_of, size_of, MaybeUninit};
 
 #[test]
@@ -97,3 +99,103 @@ fn test_error() {
         _ => panic!(),
     }
 }
+
+#[test]
+fn test_eq_hash() {
+    let data = rand::random::<[_; 8]>();
+    let frames = [
+        Frame::Data(DataFrame::new(Id::Standard(42), &data)),
+        Frame::Data(DataFrame::new(Id::Extended(42), &data)),
+        Frame::FdData(FdDataFrame::new(Id::Standard(42), false, false, &data)),
+        Frame::FdData(FdDataFrame::new(Id::Standard(42), true, false, &data)),
+        Frame::Remote(RemoteFrame::new(Id::Standard(42), 8)),
+    ];
+    let set = frames.iter().chain(&frames).collect::<HashSet<_>>();
+    assert_eq!(set.len(), frames.len());
+}
+
+#[test]
+fn test_cmp_priority() {
+    let data = Frame::Data(DataFrame::new(Id::Standard(42), &[]));
+    let fd_data = Frame::FdData(FdDataFrame::new(Id::Standard(42), false, false, &[]));
+    let remote = Frame::Remote(RemoteFrame::new(Id::Standard(42), 0));
+    let extended_data = Frame::Data(DataFrame::new(Id::Extended(42 << 18), &[]));
+    let extended_remote = Frame::Remote(RemoteFrame::new(Id::Extended(42 << 18), 0));
+    let mut frames = [extended_remote, remote, extended_data, fd_data, data];
+    frames.sort_by(Frame::cmp_priority);
+    assert_eq!(
+        frames,
+        [data, fd_data, remote, extended_data, extended_remote]
+    );
+}
+
+#[test]
+fn test_bytes() {
+    let data = rand::random::<[_; 8]>();
+    let frames = [
+        Frame::Data(DataFrame::new(Id::Standard(42), &data)),
+        Frame::Data(DataFrame::new(Id::Extended(4242), &data[..3])),
+        Frame::FdData(FdDataFrame::new(Id::Standard(42), true, false, &[0x42; 20])),
+        Frame::Remote(RemoteFrame::new(Id::Extended(4242), 8)),
+    ];
+    for frame in &frames {
+        let bytes = frame.as_bytes();
+        assert_eq!(bytes.len(), frame.size());
+        assert_eq!(&Frame::from_bytes(bytes).unwrap(), frame);
+    }
+    assert_eq!(frames[0].as_bytes()[..4], 42_u32.to_ne_bytes());
+}
+
+#[test]
+fn test_from_bytes_invalid() {
+    let mut bytes = [0; size_of::<sys::canfd_frame>()];
+    assert!(Frame::from_bytes(&bytes[..size_of::<sys::can_frame>() - 1]).is_err());
+    assert!(Frame::from_bytes(&bytes[..size_of::<sys::can_frame>() + 1]).is_err());
+
+    // data length exceeding its limit
+    bytes[4] = 9;
+    assert!(Frame::from_bytes(&bytes[..size_of::<sys::can_frame>()]).is_err());
+    bytes[4] = 65;
+    assert!(Frame::from_bytes(&bytes).is_err());
+
+    // data length matching no DLC
+    for len in 9..=11 {
+        bytes[4] = len;
+        assert!(Frame::from_bytes(&bytes).is_err());
+    }
+    bytes[4] = 12;
+    assert!(Frame::from_bytes(&bytes).is_ok());
+
+    // standard identifier exceeding 11 bits
+    bytes[4] = 0;
+    bytes[..4].copy_from_slice(&(sys::CAN_SFF_MASK + 1).to_ne_bytes());
+    assert!(Frame::from_bytes(&bytes).is_err());
+    assert!(Frame::from_bytes(&bytes[..size_of::<sys::can_frame>()]).is_err());
+    bytes[..4].copy_from_slice(&((sys::CAN_SFF_MASK + 1) | sys::CAN_EFF_FLAG).to_ne_bytes());
+    assert!(Frame::from_bytes(&bytes).is_ok());
+
+    // remote CAN FD frame
+    bytes[..4].copy_from_slice(&(42 | sys::CAN_RTR_FLAG).to_ne_bytes());
+    assert!(Frame::from_bytes(&bytes).is_err());
+}
+
+#[test]
+fn test_from_bytes_fuzz() {
+    let mut rng = rand::thread_rng();
+    for _ in 0..100_000 {
+        let len = match rng.gen_range(0..3) {
+            0 => size_of::<sys::can_frame>(),
+            1 => size_of::<sys::canfd_frame>(),
+            _ => rng.gen_range(0..=size_of::<sys::canfd_frame>() + 1),
+        };
+        let mut bytes = (0..len).map(|_| rng.gen()).collect::<Vec<u8>>();
+        // keep the length field small enough to produce valid frames from time to time
+        if len > 4 && rng.gen() {
+            bytes[4] %= 72;
+        }
+        if let Ok(frame) = Frame::from_bytes(&bytes) {
+            assert_eq!(frame.as_bytes(), &bytes[..]);
+            let _ = format!("{:?}", frame);
+        }
+    }
+}
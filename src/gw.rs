@@ -0,0 +1,273 @@
+mod checksum;
+mod flags;
+mod modification;
+mod rule;
+
+use crate::netlink::{read, Message, MessageBuilder, Netlink};
+use crate::{sys, Error, Result};
+pub use checksum::{GwCrc8Checksum, GwCrc8Profile, GwXorChecksum};
+pub use flags::{GwFlags, GwTargets};
+pub use modification::{GwModification, GwOp};
+pub use rule::{GwEntry, GwFilter, GwRule};
+use std::io;
+
+/// A netlink client managing the rules of the CAN gateway (`can-gw`),
+/// which routes frames between interfaces in the kernel.
+/// Modifying rules requires `CAP_NET_ADMIN`.
+pub struct CanGw(Netlink);
+
+impl CanGw {
+    pub fn new() -> Result<Self> {
+        Ok(Self(Netlink::route()?))
+    }
+
+    /// Adds a rule, or updates the modifications of the rule with the same `uid`.
+    pub fn add(&self, rule: &GwRule) -> Result<()> {
+        self.0
+            .request(encode(sys::RTM_NEWROUTE, rule.flags, Some(rule)))?;
+        Ok(())
+    }
+
+    /// Removes the rule equal to `rule`, or the rule with the same `uid`.
+    pub fn remove(&self, rule: &GwRule) -> Result<()> {
+        self.0
+            .request(encode(sys::RTM_DELROUTE, rule.flags, Some(rule)))?;
+        Ok(())
+    }
+
+    /// Removes all rules.
+    pub fn flush(&self) -> Result<()> {
+        self.0
+            .request(encode(sys::RTM_DELROUTE, GwFlags::empty(), None))?;
+        Ok(())
+    }
+
+    pub fn list(&self) -> Result<Vec<GwEntry>> {
+        let header = rtcanmsg(GwFlags::empty());
+        self.0
+            .request(MessageBuilder::new(
+                sys::RTM_GETROUTE,
+                sys::NLM_F_DUMP,
+                &header,
+            ))?
+            .iter()
+            .filter(|message| message.ty() == sys::RTM_NEWROUTE)
+            .map(decode)
+            .collect()
+    }
+}
+
+fn rtcanmsg(flags: GwFlags) -> sys::rtcanmsg {
+    sys::rtcanmsg {
+        can_family: libc::AF_CAN as _,
+        gwtype: sys::CGW_TYPE_CAN_CAN as _,
+        flags: flags.bits(),
+    }
+}
+
+fn encode(ty: u32, flags: GwFlags, rule: Option<&GwRule>) -> MessageBuilder {
+    let mut message = MessageBuilder::new(ty, 0, &rtcanmsg(flags));
+    let rule = match rule {
+        Some(rule) => rule,
+        // both interfaces set to zero
+        None => {
+            message
+                .attr_value(sys::CGW_SRC_IF, &0_u32)
+                .attr_value(sys::CGW_DST_IF, &0_u32);
+            return message;
+        }
+    };
+
+    let fd = rule.flags.contains(GwFlags::FD);
+    for modification in &rule.modifications {
+        let ty = match (modification.op, fd) {
+            (GwOp::And, false) => sys::CGW_MOD_AND,
+            (GwOp::Or, false) => sys::CGW_MOD_OR,
+            (GwOp::Xor, false) => sys::CGW_MOD_XOR,
+            (GwOp::Set, false) => sys::CGW_MOD_SET,
+            (GwOp::And, true) => sys::CGW_FDMOD_AND,
+            (GwOp::Or, true) => sys::CGW_FDMOD_OR,
+            (GwOp::Xor, true) => sys::CGW_FDMOD_XOR,
+            (GwOp::Set, true) => sys::CGW_FDMOD_SET,
+        };
+        message.attr(ty, &encode_modification(modification, fd));
+    }
+    if let Some(xor) = &rule.xor {
+        message.attr_value(
+            sys::CGW_CS_XOR,
+            &sys::cgw_csum_xor {
+                from_idx: xor.from,
+                to_idx: xor.to,
+                result_idx: xor.result,
+                init_xor_val: xor.init,
+            },
+        );
+    }
+    if let Some(crc8) = &rule.crc8 {
+        let mut profile_data = [0; 20];
+        let profile = match crc8.profile {
+            GwCrc8Profile::Unspecified => sys::CGW_CRC8PRF_UNSPEC,
+            GwCrc8Profile::Byte(value) => {
+                profile_data[0] = value;
+                sys::CGW_CRC8PRF_1U8
+            }
+            GwCrc8Profile::Table(table) => {
+                profile_data[..16].copy_from_slice(&table);
+                sys::CGW_CRC8PRF_16U8
+            }
+            GwCrc8Profile::SffIdXor => sys::CGW_CRC8PRF_SFFID_XOR,
+        };
+        message.attr_value(
+            sys::CGW_CS_CRC8,
+            &sys::cgw_csum_crc8 {
+                from_idx: crc8.from,
+                to_idx: crc8.to,
+                result_idx: crc8.result,
+                init_crc_val: crc8.init,
+                final_xor_val: crc8.final_xor,
+                crctab: crc8.table,
+                profile: profile as _,
+                profile_data,
+            },
+        );
+    }
+    if let Some(uid) = &rule.uid {
+        message.attr_value(sys::CGW_MOD_UID, uid);
+    }
+    if let Some(hop_limit) = &rule.hop_limit {
+        message.attr_value(sys::CGW_LIM_HOPS, hop_limit);
+    }
+    if let Some(filter) = &rule.filter {
+        message.attr_value(
+            sys::CGW_FILTER,
+            &sys::can_filter {
+                can_id: filter.can_id,
+                can_mask: filter.can_mask,
+            },
+        );
+    }
+    message
+        .attr_value(sys::CGW_SRC_IF, &rule.src_ifindex)
+        .attr_value(sys::CGW_DST_IF, &rule.dst_ifindex);
+    message
+}
+
+fn decode(message: &Message) -> Result<GwEntry> {
+    let header = message
+        .family_header::<sys::rtcanmsg>()
+        .ok_or_else(invalid_data)?;
+    let mut entry = GwEntry {
+        rule: GwRule::new(0, 0),
+        handled: 0,
+        dropped: 0,
+        deleted: 0,
+    };
+    entry.rule.flags = GwFlags::from_bits_truncate(header.flags);
+
+    for (ty, payload) in message.attrs::<sys::rtcanmsg>() {
+        match ty {
+            sys::CGW_MOD_AND | sys::CGW_MOD_OR | sys::CGW_MOD_XOR | sys::CGW_MOD_SET => entry
+                .rule
+                .modifications
+                .push(decode_modification(ty - sys::CGW_MOD_AND, payload, false)?),
+            sys::CGW_FDMOD_AND | sys::CGW_FDMOD_OR | sys::CGW_FDMOD_XOR | sys::CGW_FDMOD_SET => {
+                entry.rule.modifications.push(decode_modification(
+                    ty - sys::CGW_FDMOD_AND,
+                    payload,
+                    true,
+                )?)
+            }
+            sys::CGW_CS_XOR => {
+                let xor = read::<sys::cgw_csum_xor>(payload).ok_or_else(invalid_data)?;
+                entry.rule.xor = Some(GwXorChecksum {
+                    from: xor.from_idx,
+                    to: xor.to_idx,
+                    result: xor.result_idx,
+                    init: xor.init_xor_val,
+                });
+            }
+            sys::CGW_CS_CRC8 => {
+                let crc8 = read::<sys::cgw_csum_crc8>(payload).ok_or_else(invalid_data)?;
+                let data = crc8.profile_data;
+                entry.rule.crc8 = Some(GwCrc8Checksum {
+                    from: crc8.from_idx,
+                    to: crc8.to_idx,
+                    result: crc8.result_idx,
+                    init: crc8.init_crc_val,
+                    final_xor: crc8.final_xor_val,
+                    table: crc8.crctab,
+                    profile: match crc8.profile as u32 {
+                        sys::CGW_CRC8PRF_1U8 => GwCrc8Profile::Byte(data[0]),
+                        sys::CGW_CRC8PRF_16U8 => {
+                            let mut table = [0; 16];
+                            table.copy_from_slice(&data[..16]);
+                            GwCrc8Profile::Table(table)
+                        }
+                        sys::CGW_CRC8PRF_SFFID_XOR => GwCrc8Profile::SffIdXor,
+                        _ => GwCrc8Profile::Unspecified,
+                    },
+                });
+            }
+            sys::CGW_HANDLED => entry.handled = read(payload).ok_or_else(invalid_data)?,
+            sys::CGW_DROPPED => entry.dropped = read(payload).ok_or_else(invalid_data)?,
+            sys::CGW_DELETED => entry.deleted = read(payload).ok_or_else(invalid_data)?,
+            sys::CGW_SRC_IF => entry.rule.src_ifindex = read(payload).ok_or_else(invalid_data)?,
+            sys::CGW_DST_IF => entry.rule.dst_ifindex = read(payload).ok_or_else(invalid_data)?,
+            sys::CGW_FILTER => {
+                let filter = read::<sys::can_filter>(payload).ok_or_else(invalid_data)?;
+                entry.rule.filter = Some(GwFilter {
+                    can_id: filter.can_id,
+                    can_mask: filter.can_mask,
+                });
+            }
+            sys::CGW_LIM_HOPS => {
+                entry.rule.hop_limit = Some(read(payload).ok_or_else(invalid_data)?)
+            }
+            sys::CGW_MOD_UID => entry.rule.uid = Some(read(payload).ok_or_else(invalid_data)?),
+            _ => (),
+        }
+    }
+    Ok(entry)
+}
+
+// struct cgw_frame_mod or struct cgw_fdframe_mod:
+// the fields at the offsets of struct can_frame or struct canfd_frame, followed by the targets
+fn encode_modification(modification: &GwModification, fd: bool) -> Vec<u8> {
+    let mut payload = modification.can_id.to_ne_bytes().to_vec();
+    if fd {
+        payload.extend_from_slice(&[modification.len, modification.flags, 0, 0]);
+        payload.extend_from_slice(&modification.data);
+    } else {
+        payload.extend_from_slice(&[modification.len, 0, 0, modification.len8_dlc]);
+        payload.extend_from_slice(&modification.data[..sys::CAN_MAX_DLEN as _]);
+    }
+    payload.push(modification.targets.bits());
+    payload
+}
+
+fn decode_modification(op: u32, payload: &[u8], fd: bool) -> Result<GwModification> {
+    let size = if fd { sys::CANFD_MTU } else { sys::CAN_MTU };
+    if payload.len() < size + 1 {
+        return Err(invalid_data());
+    }
+    let mut modification = GwModification::new(
+        [GwOp::And, GwOp::Or, GwOp::Xor, GwOp::Set][op as usize],
+        GwTargets::from_bits_truncate(payload[size]),
+    );
+    modification.can_id = read(payload).ok_or_else(invalid_data)?;
+    modification.len = payload[4];
+    if fd {
+        modification.flags = payload[5];
+    } else {
+        modification.len8_dlc = payload[7];
+    }
+    modification.data[..size - 8].copy_from_slice(&payload[8..size]);
+    Ok(modification)
+}
+
+fn invalid_data() -> Error {
+    io::Error::new(io::ErrorKind::InvalidData, "malformed can-gw message").into()
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,60 @@
+/// An XOR checksum of `data[from..=to]` stored into `data[result]`.
+/// Negative indices count from the end of the data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GwXorChecksum {
+    pub from: i8,
+    pub to: i8,
+    pub result: i8,
+    pub init: u8,
+}
+
+/// A CRC8 checksum of `data[from..=to]` stored into `data[result]`.
+/// Negative indices count from the end of the data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GwCrc8Checksum {
+    pub from: i8,
+    pub to: i8,
+    pub result: i8,
+    pub init: u8,
+    pub final_xor: u8,
+    pub table: [u8; 256],
+    pub profile: GwCrc8Profile,
+}
+
+impl GwCrc8Checksum {
+    /// Computes the table of the polynomial `poly` (MSB first, e.g. 0x1d for SAE J1850),
+    /// with zero as the initial value and the final XOR value.
+    pub fn new(from: i8, to: i8, result: i8, poly: u8) -> Self {
+        let mut table = [0; 256];
+        for (i, crc) in table.iter_mut().enumerate() {
+            *crc = (0..8).fold(i as u8, |crc, _| {
+                if crc & 0x80 != 0 {
+                    crc << 1 ^ poly
+                } else {
+                    crc << 1
+                }
+            });
+        }
+        Self {
+            from,
+            to,
+            result,
+            init: 0,
+            final_xor: 0,
+            table,
+            profile: GwCrc8Profile::Unspecified,
+        }
+    }
+}
+
+/// An additional value included in the CRC8 checksum, used by AUTOSAR E2E profiles.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GwCrc8Profile {
+    Unspecified,
+    /// A constant value.
+    Byte(u8),
+    /// A value selected by the lower 4 bits of `data[1]`.
+    Table([u8; 16]),
+    /// `(can_id & 0xff) ^ (can_id >> 8 & 0xff)`
+    SffIdXor,
+}
@@ -0,0 +1,26 @@
+use crate::sys;
+
+bitflags::bitflags! {
+    pub struct GwFlags: u16 {
+        /// Routed frames are echoed back to the sockets on the destination interface.
+        const CAN_ECHO = sys::CGW_FLAGS_CAN_ECHO as _;
+        /// Keeps the timestamp of the source frames.
+        const SRC_TSTAMP = sys::CGW_FLAGS_CAN_SRC_TSTAMP as _;
+        /// Allows routing to the source interface.
+        const IIF_TX_OK = sys::CGW_FLAGS_CAN_IIF_TX_OK as _;
+        /// Routes CAN FD frames instead of classic frames.
+        const FD = sys::CGW_FLAGS_CAN_FD as _;
+    }
+}
+
+bitflags::bitflags! {
+    /// Fields of a frame to be modified.
+    pub struct GwTargets: u8 {
+        const ID = sys::CGW_MOD_ID as _;
+        /// The DLC of classic frames or the length of CAN FD frames.
+        const LEN = sys::CGW_MOD_LEN as _;
+        const DATA = sys::CGW_MOD_DATA as _;
+        /// The flags of CAN FD frames (BRS and ESI).
+        const FLAGS = sys::CGW_MOD_FLAGS as _;
+    }
+}
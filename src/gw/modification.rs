@@ -0,0 +1,57 @@
+use crate::GwTargets;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GwOp {
+    And,
+    Or,
+    Xor,
+    /// Replaces the fields with the values.
+    Set,
+}
+
+/// A modification of routed frames, which applies `op` with the fields to the `targets`.
+///
+/// The fields are the raw values of `struct can_frame` for classic rules
+/// and `struct canfd_frame` for CAN FD rules, so that masks need not be valid frames
+/// (e.g. an AND mask of `0xff` for the length, or of the `CAN_EFF_FLAG` bit of the identifier).
+/// Each operation is applied at most once in a rule,
+/// in the order of AND, OR, XOR and SET.
+///
+/// ```
+/// use socketcan_alt::{GwModification, GwOp, GwTargets};
+///
+/// // set the identifier to 0x123
+/// let modification = GwModification {
+///     can_id: 0x123,
+///     ..GwModification::new(GwOp::Set, GwTargets::ID)
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GwModification {
+    pub op: GwOp,
+    pub targets: GwTargets,
+    /// Including the flags (`CAN_EFF_FLAG`, `CAN_RTR_FLAG` and `CAN_ERR_FLAG`).
+    pub can_id: u32,
+    pub len: u8,
+    /// The flags of CAN FD frames, for CAN FD rules only.
+    pub flags: u8,
+    /// The raw DLC of classic frames, for classic rules only.
+    pub len8_dlc: u8,
+    /// Classic rules use the first 8 bytes.
+    pub data: [u8; 64],
+}
+
+impl GwModification {
+    /// Creates a modification with all fields zero.
+    pub fn new(op: GwOp, targets: GwTargets) -> Self {
+        Self {
+            op,
+            targets,
+            can_id: 0,
+            len: 0,
+            flags: 0,
+            len8_dlc: 0,
+            data: [0; 64],
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use crate::{GwCrc8Checksum, GwFlags, GwModification, GwXorChecksum};
+
+/// A routing rule of the CAN gateway (`can-gw`) between interfaces.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct GwRule {
+    pub src_ifindex: u32,
+    pub dst_ifindex: u32,
+    pub flags: GwFlags,
+    /// Routes frames matching `(can_id & can_mask) == (filter.can_id & can_mask)` as `CAN_RAW` filters.
+    /// `None` routes all frames.
+    pub filter: Option<GwFilter>,
+    pub modifications: Vec<GwModification>,
+    pub xor: Option<GwXorChecksum>,
+    pub crc8: Option<GwCrc8Checksum>,
+    /// Limits the number of hops of routed frames.
+    pub hop_limit: Option<u8>,
+    /// Identifies the rule so that adding a rule with the same identifier updates its modifications.
+    pub uid: Option<u32>,
+}
+
+impl GwRule {
+    pub fn new(src_ifindex: u32, dst_ifindex: u32) -> Self {
+        Self {
+            src_ifindex,
+            dst_ifindex,
+            flags: GwFlags::empty(),
+            filter: None,
+            modifications: Vec::new(),
+            xor: None,
+            crc8: None,
+            hop_limit: None,
+            uid: None,
+        }
+    }
+}
+
+/// `can_id` and `can_mask` include the flags (`CAN_EFF_FLAG` and `CAN_RTR_FLAG`) as in `struct can_filter`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GwFilter {
+    pub can_id: u32,
+    pub can_mask: u32,
+}
+
+/// A rule with its statistics.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GwEntry {
+    pub rule: GwRule,
+    /// Number of routed frames.
+    pub handled: u32,
+    /// Number of frames dropped because of errors of the destination interface.
+    pub dropped: u32,
+    /// Number of frames deleted because of the hop limit or invalid modifications.
+    pub deleted: u32,
+}
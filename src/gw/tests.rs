@@ -0,0 +1,215 @@
+use super::{decode, encode, rtcanmsg, CanGw};
+use crate::netlink::{Message, MessageBuilder};
+use crate::socket::if_nametoindex;
+use crate::socket::tests::{ifname, LOCK};
+use crate::{
+    sys, Error, GwCrc8Checksum, GwCrc8Profile, GwFilter, GwFlags, GwModification, GwOp, GwRule,
+    GwTargets, GwXorChecksum,
+};
+
+macro_rules! lock {
+    (shared) => {
+        let _lock = LOCK.read();
+    };
+    (exclusive) => {
+        let _lock = LOCK.write();
+    };
+}
+
+fn roundtrip(message: MessageBuilder) -> Message {
+    let mut messages = Message::parse(&message.finish());
+    assert_eq!(messages.len(), 1);
+    messages.pop().unwrap()
+}
+
+fn random_rule() -> GwRule {
+    let mut rule = GwRule::new(rand::random(), rand::random());
+    rule.filter = Some(GwFilter {
+        can_id: 0x42,
+        can_mask: sys::CAN_SFF_MASK,
+    });
+    let mut and = GwModification::new(GwOp::And, GwTargets::ID | GwTargets::DATA);
+    and.can_id = sys::CAN_SFF_MASK;
+    and.data[..8].copy_from_slice(&rand::random::<[_; 8]>());
+    rule.modifications.push(and);
+    rule.modifications.push(GwModification {
+        len: 8,
+        len8_dlc: 8,
+        ..GwModification::new(GwOp::Set, GwTargets::LEN)
+    });
+    rule.xor = Some(GwXorChecksum {
+        from: 0,
+        to: 6,
+        result: 7,
+        init: 0xff,
+    });
+    let mut crc8 = GwCrc8Checksum::new(0, -2, -1, 0x1d);
+    crc8.profile = GwCrc8Profile::Table(rand::random());
+    rule.crc8 = Some(crc8);
+    rule.hop_limit = Some(3);
+    rule.uid = Some(rand::random());
+    rule
+}
+
+#[test]
+fn test_crc8_table() {
+    // CRC-8/SAE-J1850 without the initial and final XOR values
+    let crc8 = GwCrc8Checksum::new(0, 7, 8, 0x1d);
+    assert_eq!(crc8.table[0], 0x00);
+    assert_eq!(crc8.table[1], 0x1d);
+    assert_eq!(crc8.table[0x80], 0x26);
+    assert_eq!(crc8.table[0xff], 0xc4);
+    let crc = b"123456789"
+        .iter()
+        .fold(0_u8, |crc, &byte| crc8.table[(crc ^ byte) as usize]);
+    assert_eq!(crc, 0x37);
+}
+
+#[test]
+fn test_encode_modification() {
+    let mut rule = GwRule::new(1, 2);
+    rule.modifications.push(GwModification {
+        can_id: 0x42,
+        len: 3,
+        data: [0xff; 64],
+        ..GwModification::new(GwOp::Xor, GwTargets::DATA)
+    });
+    let message = roundtrip(encode(sys::RTM_NEWROUTE, rule.flags, Some(&rule)));
+    let (ty, payload) = message.attrs::<sys::rtcanmsg>().next().unwrap();
+    assert_eq!(ty, sys::CGW_MOD_XOR);
+    assert_eq!(payload.len(), sys::CAN_MTU + 1);
+    assert_eq!(payload[..4], 0x42_u32.to_ne_bytes());
+    assert_eq!(payload[4..8], [3, 0, 0, 0]);
+    assert_eq!(payload[8..sys::CAN_MTU], [0xff; 8]);
+    assert_eq!(payload[sys::CAN_MTU], sys::CGW_MOD_DATA as u8);
+}
+
+#[test]
+fn test_encode_modification_fd() {
+    let mut rule = GwRule::new(1, 2);
+    rule.flags = GwFlags::FD;
+    rule.modifications.push(GwModification {
+        flags: sys::CANFD_BRS as _,
+        ..GwModification::new(GwOp::Set, GwTargets::FLAGS)
+    });
+    let message = roundtrip(encode(sys::RTM_NEWROUTE, rule.flags, Some(&rule)));
+    let (ty, payload) = message.attrs::<sys::rtcanmsg>().next().unwrap();
+    assert_eq!(ty, sys::CGW_FDMOD_SET);
+    assert_eq!(payload.len(), sys::CANFD_MTU + 1);
+    assert_eq!(payload[5], sys::CANFD_BRS as u8);
+    assert_eq!(payload[sys::CANFD_MTU], sys::CGW_MOD_FLAGS as u8);
+}
+
+#[test]
+fn test_decode_raw_mask() {
+    // masks as made by cangw, which are not valid frames
+    let mut payload = u32::MAX.to_ne_bytes().to_vec();
+    payload.extend_from_slice(&[0xff, 0xff, 0, 0]);
+    payload.extend_from_slice(&[0xff; 64]);
+    payload.push((sys::CGW_MOD_ID | sys::CGW_MOD_LEN) as u8);
+    let mut message = MessageBuilder::new(sys::RTM_NEWROUTE, 0, &rtcanmsg(GwFlags::FD));
+    message.attr(sys::CGW_FDMOD_AND, &payload);
+
+    let entry = decode(&roundtrip(message)).unwrap();
+    let modification = entry.rule.modifications[0];
+    assert_eq!(modification.op, GwOp::And);
+    assert_eq!(modification.targets, GwTargets::ID | GwTargets::LEN);
+    assert_eq!(modification.can_id, u32::MAX);
+    assert_eq!(modification.len, 0xff);
+    assert_eq!(modification.flags, 0xff);
+    assert_eq!(modification.data, [0xff; 64]);
+}
+
+#[test]
+fn test_encode_flush() {
+    let message = roundtrip(encode(sys::RTM_DELROUTE, GwFlags::empty(), None));
+    assert_eq!(message.ty(), sys::RTM_DELROUTE);
+    let header = message.family_header::<sys::rtcanmsg>().unwrap();
+    assert_eq!(header.can_family as i32, libc::AF_CAN);
+    assert_eq!(header.gwtype as u32, sys::CGW_TYPE_CAN_CAN);
+    let attrs = message.attrs::<sys::rtcanmsg>().collect::<Vec<_>>();
+    assert_eq!(
+        attrs,
+        vec![
+            (sys::CGW_SRC_IF, &[0_u8; 4][..]),
+            (sys::CGW_DST_IF, &[0_u8; 4][..])
+        ]
+    );
+}
+
+#[test]
+fn test_roundtrip() {
+    let rule = random_rule();
+    let entry = decode(&roundtrip(encode(
+        sys::RTM_NEWROUTE,
+        rule.flags,
+        Some(&rule),
+    )))
+    .unwrap();
+    assert_eq!(entry.rule, rule);
+    assert_eq!((entry.handled, entry.dropped, entry.deleted), (0, 0, 0));
+}
+
+#[test]
+fn test_roundtrip_fd() {
+    let mut rule = GwRule::new(1, 2);
+    rule.flags = GwFlags::FD | GwFlags::CAN_ECHO;
+    rule.modifications.push(GwModification {
+        can_id: 0x42 | sys::CAN_EFF_FLAG,
+        len: 64,
+        flags: sys::CANFD_BRS as _,
+        data: [0xff; 64],
+        ..GwModification::new(GwOp::Or, GwTargets::DATA | GwTargets::FLAGS)
+    });
+    let entry = decode(&roundtrip(encode(
+        sys::RTM_NEWROUTE,
+        rule.flags,
+        Some(&rule),
+    )))
+    .unwrap();
+    assert_eq!(entry.rule, rule);
+}
+
+#[test]
+#[ignore]
+fn test_add_list_remove() {
+    lock!(exclusive);
+    let ifindex = if_nametoindex(ifname()).unwrap();
+    let mut rule = random_rule();
+    rule.src_ifindex = ifindex;
+    rule.dst_ifindex = ifindex;
+    rule.flags = GwFlags::IIF_TX_OK;
+
+    let gw = CanGw::new().unwrap();
+    gw.add(&rule).unwrap();
+    assert!(gw.list().unwrap().iter().any(|entry| entry.rule == rule));
+    gw.remove(&rule).unwrap();
+    assert!(!gw.list().unwrap().iter().any(|entry| entry.rule == rule));
+}
+
+#[test]
+#[ignore]
+fn test_flush() {
+    lock!(exclusive);
+    let ifindex = if_nametoindex(ifname()).unwrap();
+    let mut rule = GwRule::new(ifindex, ifindex);
+    rule.flags = GwFlags::IIF_TX_OK;
+
+    let gw = CanGw::new().unwrap();
+    gw.add(&rule).unwrap();
+    gw.flush().unwrap();
+    assert!(gw.list().unwrap().is_empty());
+}
+
+#[test]
+#[ignore]
+fn test_add_no_device() {
+    let gw = CanGw::new().unwrap();
+    assert!(matches!(
+        gw.add(&GwRule::new(u32::MAX, u32::MAX)),
+        Err(Error::Netlink {
+            errno: libc::ENODEV,
+            ..
+        })
+    ));
+}
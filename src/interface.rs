@@ -0,0 +1,100 @@
+mod flags;
+mod link_kind;
+mod mtu_class;
+mod oper_state;
+mod watcher;
+
+use crate::netlink::{read, read_string, Attrs, Message, MessageBuilder, Netlink};
+use crate::{sys, Result};
+pub use flags::InterfaceFlags;
+pub use link_kind::LinkKind;
+pub use mtu_class::MtuClass;
+pub use oper_state::OperState;
+use std::fs;
+use std::mem::MaybeUninit;
+pub use watcher::{InterfaceEvent, InterfaceWatcher};
+
+/// A CAN network interface.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Interface {
+    pub index: u32,
+    pub name: String,
+    pub kind: LinkKind,
+    pub mtu: usize,
+    pub mtu_class: MtuClass,
+    pub operstate: OperState,
+    pub flags: InterfaceFlags,
+}
+
+/// Lists the CAN interfaces of the network namespace, ordered by index.
+pub fn list_interfaces() -> Result<Vec<Interface>> {
+    let mut header = unsafe { MaybeUninit::<sys::ifinfomsg>::zeroed().assume_init() };
+    header.ifi_family = libc::AF_UNSPEC as _;
+    let netlink = Netlink::route()?;
+    let mut interfaces = netlink
+        .request(MessageBuilder::new(
+            sys::RTM_GETLINK,
+            sys::NLM_F_DUMP,
+            &header,
+        ))?
+        .iter()
+        .filter(|message| message.ty() == sys::RTM_NEWLINK)
+        .filter_map(decode)
+        .collect::<Vec<_>>();
+    for interface in &mut interfaces {
+        if let LinkKind::Physical { driver } = &mut interface.kind {
+            *driver = driver_name(&interface.name);
+        }
+    }
+    interfaces.sort_by_key(|interface| interface.index);
+    Ok(interfaces)
+}
+
+// decodes RTM_NEWLINK or RTM_DELLINK, None for other than CAN interfaces
+pub(crate) fn decode(message: &Message) -> Option<Interface> {
+    let header = message.family_header::<sys::ifinfomsg>()?;
+    if header.ifi_type != libc::ARPHRD_CAN {
+        return None;
+    }
+    let mut interface = Interface {
+        index: header.ifi_index as _,
+        name: String::new(),
+        kind: LinkKind::Unknown,
+        mtu: 0,
+        mtu_class: MtuClass::Classic,
+        operstate: OperState::Unknown,
+        flags: InterfaceFlags::from_bits_truncate(header.ifi_flags),
+    };
+    for (ty, payload) in message.attrs::<sys::ifinfomsg>() {
+        match ty {
+            sys::IFLA_IFNAME => interface.name = read_string(payload),
+            sys::IFLA_MTU => interface.mtu = read::<u32>(payload)? as _,
+            sys::IFLA_OPERSTATE => interface.operstate = OperState::from_raw(read(payload)?),
+            sys::IFLA_LINKINFO => {
+                for (ty, payload) in Attrs(payload) {
+                    if ty == sys::IFLA_INFO_KIND {
+                        interface.kind = match read_string(payload).as_str() {
+                            "vcan" => LinkKind::Vcan,
+                            "vxcan" => LinkKind::Vxcan,
+                            "can" => LinkKind::Physical { driver: None },
+                            kind => LinkKind::Other(kind.to_owned()),
+                        };
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    interface.mtu_class = MtuClass::from_mtu(interface.mtu);
+    Some(interface)
+}
+
+// rtnetlink reports "can" for all controllers
+fn driver_name(ifname: &str) -> Option<String> {
+    let path = fs::read_link(format!("/sys/class/net/{}/device/driver", ifname)).ok()?;
+    Some(path.file_name()?.to_string_lossy().into_owned())
+}
+
+#[cfg(test)]
+mod tests;
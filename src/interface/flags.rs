@@ -0,0 +1,14 @@
+bitflags::bitflags! {
+    /// Flags of a network interface (`IFF_*`).
+    pub struct InterfaceFlags: u32 {
+        /// The interface is administratively up.
+        const UP = libc::IFF_UP as _;
+        const RUNNING = libc::IFF_RUNNING as _;
+        const NOARP = libc::IFF_NOARP as _;
+        /// The carrier of the link is up.
+        const LOWER_UP = libc::IFF_LOWER_UP as _;
+        const DORMANT = libc::IFF_DORMANT as _;
+        /// The driver echoes sent frames back on completion of the transmission.
+        const ECHO = libc::IFF_ECHO as _;
+    }
+}
@@ -0,0 +1,14 @@
+/// The kind of a CAN interface, reported by its `rtnl_link_ops`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum LinkKind {
+    /// A virtual CAN interface (`vcan`).
+    Vcan,
+    /// One end of a virtual CAN tunnel (`vxcan`).
+    Vxcan,
+    /// A CAN controller, with the name of the driver if it is bound to a device.
+    Physical { driver: Option<String> },
+    /// A kind not known to this crate.
+    Other(String),
+    /// The driver does not report its kind, e.g. old `slcan`.
+    Unknown,
+}
@@ -0,0 +1,33 @@
+use crate::sys;
+
+/// The kind of frames an interface carries, according to its MTU.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MtuClass {
+    /// Classic CAN frames only (`CAN_MTU`).
+    Classic,
+    /// CAN FD frames as well (`CANFD_MTU`).
+    Fd,
+    /// CAN XL frames as well (`CANXL_MIN_MTU` or more).
+    Xl,
+}
+
+impl MtuClass {
+    pub fn from_mtu(mtu: usize) -> Self {
+        if mtu >= sys::CANXL_MIN_MTU {
+            Self::Xl
+        } else if mtu >= sys::CANFD_MTU {
+            Self::Fd
+        } else {
+            Self::Classic
+        }
+    }
+
+    /// The largest MTU of the class, which virtual interfaces use.
+    pub fn mtu(self) -> usize {
+        match self {
+            Self::Classic => sys::CAN_MTU,
+            Self::Fd => sys::CANFD_MTU,
+            Self::Xl => sys::CANXL_MTU,
+        }
+    }
+}
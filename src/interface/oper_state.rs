@@ -0,0 +1,27 @@
+use crate::sys;
+
+/// The operational state of an interface (RFC 2863).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OperState {
+    Unknown,
+    NotPresent,
+    Down,
+    LowerLayerDown,
+    Testing,
+    Dormant,
+    Up,
+}
+
+impl OperState {
+    pub(crate) fn from_raw(value: u8) -> Self {
+        match value as u32 {
+            sys::IF_OPER_NOTPRESENT => Self::NotPresent,
+            sys::IF_OPER_DOWN => Self::Down,
+            sys::IF_OPER_LOWERLAYERDOWN => Self::LowerLayerDown,
+            sys::IF_OPER_TESTING => Self::Testing,
+            sys::IF_OPER_DORMANT => Self::Dormant,
+            sys::IF_OPER_UP => Self::Up,
+            _ => Self::Unknown,
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use super::{decode, list_interfaces, InterfaceFlags, LinkKind, MtuClass, OperState};
+use crate::netlink::{Message, MessageBuilder};
+use crate::socket::tests::{ifname, LOCK};
+use crate::sys;
+use std::mem::MaybeUninit;
+
+macro_rules! lock {
+    (shared) => {
+        let _lock = LOCK.read();
+    };
+    (exclusive) => {
+        let _lock = LOCK.write();
+    };
+}
+
+fn newlink(ty: u16, kind: Option<&str>) -> Message {
+    let mut header = unsafe { MaybeUninit::<sys::ifinfomsg>::zeroed().assume_init() };
+    header.ifi_type = ty;
+    header.ifi_index = 42;
+    header.ifi_flags = (libc::IFF_UP | libc::IFF_RUNNING | libc::IFF_ECHO) as _;
+    let mut message = MessageBuilder::new(sys::RTM_NEWLINK, 0, &header);
+    message
+        .attr(sys::IFLA_IFNAME, b"can0\0")
+        .attr_value(sys::IFLA_MTU, &(sys::CANFD_MTU as u32))
+        .attr_value(sys::IFLA_OPERSTATE, &(sys::IF_OPER_UP as u8));
+    if let Some(kind) = kind {
+        // IFLA_LINKINFO { IFLA_INFO_KIND }
+        let mut linkinfo = ((4 + kind.len() + 1) as u16).to_ne_bytes().to_vec();
+        linkinfo.extend_from_slice(&(sys::IFLA_INFO_KIND as u16).to_ne_bytes());
+        linkinfo.extend_from_slice(kind.as_bytes());
+        linkinfo.push(0);
+        message.attr(sys::IFLA_LINKINFO, &linkinfo);
+    }
+    Message::parse(&message.finish()).pop().unwrap()
+}
+
+#[test]
+fn test_mtu_class() {
+    assert_eq!(MtuClass::from_mtu(sys::CAN_MTU), MtuClass::Classic);
+    assert_eq!(MtuClass::from_mtu(sys::CANFD_MTU), MtuClass::Fd);
+    assert_eq!(MtuClass::from_mtu(sys::CANXL_MIN_MTU), MtuClass::Xl);
+    assert_eq!(MtuClass::from_mtu(2060), MtuClass::Xl);
+}
+
+#[test]
+fn test_decode() {
+    let interface = decode(&newlink(libc::ARPHRD_CAN, Some("vcan"))).unwrap();
+    assert_eq!(interface.index, 42);
+    assert_eq!(interface.name, "can0");
+    assert_eq!(interface.kind, LinkKind::Vcan);
+    assert_eq!(interface.mtu, sys::CANFD_MTU);
+    assert_eq!(interface.mtu_class, MtuClass::Fd);
+    assert_eq!(interface.operstate, OperState::Up);
+    assert_eq!(
+        interface.flags,
+        InterfaceFlags::UP | InterfaceFlags::RUNNING | InterfaceFlags::ECHO
+    );
+}
+
+#[test]
+fn test_decode_kind() {
+    let kind = |kind| decode(&newlink(libc::ARPHRD_CAN, kind)).unwrap().kind;
+    assert_eq!(kind(Some("vxcan")), LinkKind::Vxcan);
+    assert_eq!(kind(Some("can")), LinkKind::Physical { driver: None });
+    assert_eq!(kind(Some("foo")), LinkKind::Other("foo".to_owned()));
+    assert_eq!(kind(None), LinkKind::Unknown);
+}
+
+#[test]
+fn test_decode_not_can() {
+    assert!(decode(&newlink(libc::ARPHRD_ETHER, None)).is_none());
+}
+
+#[test]
+#[ignore]
+fn test_list_interfaces() {
+    lock!(shared);
+    let interfaces = list_interfaces().unwrap();
+    let ifname = ifname().into_string().unwrap();
+    let interface = interfaces
+        .iter()
+        .find(|interface| interface.name == ifname)
+        .unwrap();
+    assert!(interface.flags.contains(InterfaceFlags::UP));
+    assert!(interface.mtu >= sys::CAN_MTU);
+    assert!(interfaces.windows(2).all(|w| w[0].index < w[1].index));
+}
@@ -0,0 +1,118 @@
+use super::{decode, list_interfaces, Interface, InterfaceFlags};
+use crate::netlink::{Message, Netlink};
+use crate::{sys, Result};
+use std::collections::{HashMap, VecDeque};
+use std::os::unix::io::{AsRawFd, RawFd};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InterfaceEvent {
+    Added(Interface),
+    Removed(Interface),
+    Up(Interface),
+    Down(Interface),
+}
+
+/// A subscription to CAN interfaces appearing and disappearing, iterating over [`InterfaceEvent`]s.
+///
+/// Interfaces present when the watcher is created are not reported as added.
+/// An interface added in the up state is reported as added, then up.
+/// A renamed interface is reported as removed and added again,
+/// since hotplugged devices are typically renamed by udev after they appear.
+///
+/// Fails with `ENOBUFS` if notifications were dropped because they were not received in time.
+pub struct InterfaceWatcher {
+    netlink: Netlink,
+    known: HashMap<u32, Interface>,
+    pending: VecDeque<InterfaceEvent>,
+}
+
+impl InterfaceWatcher {
+    pub fn new() -> Result<Self> {
+        // subscribe before listing not to miss interfaces in between
+        let netlink = Netlink::new(sys::NETLINK_ROUTE, sys::RTMGRP_LINK)?;
+        let known = list_interfaces()?
+            .into_iter()
+            .map(|interface| (interface.index, interface))
+            .collect();
+        Ok(Self {
+            netlink,
+            known,
+            pending: VecDeque::new(),
+        })
+    }
+
+    pub fn set_nonblocking(&self, nonblocking: bool) -> Result<()> {
+        self.netlink.set_nonblocking(nonblocking)
+    }
+
+    pub fn recv(&mut self) -> Result<InterfaceEvent> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Ok(event);
+            }
+            for message in self.netlink.recv()? {
+                self.handle(&message);
+            }
+        }
+    }
+
+    fn handle(&mut self, message: &Message) {
+        let interface = match decode(message) {
+            Some(interface) => interface,
+            None => return,
+        };
+        if message.ty() == sys::RTM_DELLINK {
+            let interface = self.known.remove(&interface.index).unwrap_or(interface);
+            self.pending.push_back(InterfaceEvent::Removed(interface));
+            return;
+        }
+        if message.ty() != sys::RTM_NEWLINK {
+            return;
+        }
+        let up = interface.flags.contains(InterfaceFlags::UP);
+        match self.known.insert(interface.index, interface.clone()) {
+            Some(old) if old.name != interface.name => {
+                self.pending.push_back(InterfaceEvent::Removed(old));
+            }
+            Some(old) => {
+                if old.flags.contains(InterfaceFlags::UP) != up {
+                    self.pending.push_back(if up {
+                        InterfaceEvent::Up(interface)
+                    } else {
+                        InterfaceEvent::Down(interface)
+                    });
+                }
+                return;
+            }
+            None => (),
+        }
+        self.pending
+            .push_back(InterfaceEvent::Added(interface.clone()));
+        if up {
+            self.pending.push_back(InterfaceEvent::Up(interface));
+        }
+    }
+
+    // events received but not returned yet, which do not make the socket readable
+    #[cfg(feature = "aio")]
+    pub(crate) fn pop_pending(&mut self) -> Option<InterfaceEvent> {
+        self.pending.pop_front()
+    }
+}
+
+impl Iterator for InterfaceWatcher {
+    type Item = Result<InterfaceEvent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.recv())
+    }
+}
+
+impl AsRawFd for InterfaceWatcher {
+    fn as_raw_fd(&self) -> RawFd {
+        self.netlink.as_raw_fd()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,96 @@
+use super::{InterfaceEvent, InterfaceWatcher};
+use crate::netlink::{Message, MessageBuilder};
+use crate::socket::tests::{ifname, LOCK};
+use crate::{sys, CanLink};
+use std::mem::MaybeUninit;
+
+macro_rules! lock {
+    (shared) => {
+        let _lock = LOCK.read();
+    };
+    (exclusive) => {
+        let _lock = LOCK.write();
+    };
+}
+
+fn message(ty: u32, arphrd: u16, name: &str, up: bool) -> Message {
+    let mut header = unsafe { MaybeUninit::<sys::ifinfomsg>::zeroed().assume_init() };
+    header.ifi_type = arphrd;
+    header.ifi_index = 4242;
+    header.ifi_flags = if up { libc::IFF_UP as _ } else { 0 };
+    let mut message = MessageBuilder::new(ty, 0, &header);
+    message.attr_string(sys::IFLA_IFNAME, name);
+    Message::parse(&message.finish()).pop().unwrap()
+}
+
+fn events(watcher: &mut InterfaceWatcher) -> Vec<(&'static str, String)> {
+    watcher
+        .pending
+        .drain(..)
+        .map(|event| match event {
+            InterfaceEvent::Added(interface) => ("added", interface.name),
+            InterfaceEvent::Removed(interface) => ("removed", interface.name),
+            InterfaceEvent::Up(interface) => ("up", interface.name),
+            InterfaceEvent::Down(interface) => ("down", interface.name),
+        })
+        .collect()
+}
+
+#[test]
+fn test_handle() {
+    let mut watcher = InterfaceWatcher::new().unwrap();
+    let arphrd = libc::ARPHRD_CAN;
+
+    watcher.handle(&message(sys::RTM_NEWLINK, arphrd, "can9", false));
+    assert_eq!(events(&mut watcher), [("added", "can9".to_owned())]);
+    // renamed by udev
+    watcher.handle(&message(sys::RTM_NEWLINK, arphrd, "usbcan", false));
+    assert_eq!(
+        events(&mut watcher),
+        [
+            ("removed", "can9".to_owned()),
+            ("added", "usbcan".to_owned())
+        ]
+    );
+    watcher.handle(&message(sys::RTM_NEWLINK, arphrd, "usbcan", true));
+    assert_eq!(events(&mut watcher), [("up", "usbcan".to_owned())]);
+    // other changes
+    watcher.handle(&message(sys::RTM_NEWLINK, arphrd, "usbcan", true));
+    assert!(events(&mut watcher).is_empty());
+    watcher.handle(&message(sys::RTM_NEWLINK, arphrd, "usbcan", false));
+    assert_eq!(events(&mut watcher), [("down", "usbcan".to_owned())]);
+    watcher.handle(&message(sys::RTM_DELLINK, arphrd, "usbcan", false));
+    assert_eq!(events(&mut watcher), [("removed", "usbcan".to_owned())]);
+
+    watcher.handle(&message(sys::RTM_NEWLINK, arphrd, "can9", true));
+    assert_eq!(
+        events(&mut watcher),
+        [("added", "can9".to_owned()), ("up", "can9".to_owned())]
+    );
+}
+
+#[test]
+fn test_handle_not_can() {
+    let mut watcher = InterfaceWatcher::new().unwrap();
+    watcher.handle(&message(sys::RTM_NEWLINK, libc::ARPHRD_ETHER, "eth9", true));
+    assert!(events(&mut watcher).is_empty());
+}
+
+#[test]
+#[ignore]
+fn test_up_down() {
+    lock!(exclusive);
+    let link = CanLink::new(ifname()).unwrap();
+    let mut watcher = InterfaceWatcher::new().unwrap();
+    link.set_up(false).unwrap();
+    link.set_up(true).unwrap();
+
+    match watcher.recv().unwrap() {
+        InterfaceEvent::Down(interface) => assert_eq!(interface.index, link.ifindex()),
+        event => panic!("{:?}", event),
+    }
+    match watcher.recv().unwrap() {
+        InterfaceEvent::Up(interface) => assert_eq!(interface.index, link.ifindex()),
+        event => panic!("{:?}", event),
+    }
+}
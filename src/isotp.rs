@@ -0,0 +1,116 @@
+mod flags;
+mod options;
+
+use crate::socket::{address, if_nametoindex, setsockopt, socket};
+use crate::{sys, Error, Id, Result};
+pub use flags::IsoTpFlags;
+pub use options::IsoTpOptions;
+use std::ffi::CStr;
+use std::mem::{self, size_of_val};
+use std::os::raw::c_int;
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+
+/// A socket of ISO 15765-2 transport protocol (`CAN_ISOTP`), which sends and receives whole PDUs.
+/// The kernel segments them into frames and handles flow control.
+pub struct IsoTpSocket {
+    fd: RawFd,
+}
+
+impl IsoTpSocket {
+    /// Binds a socket receiving frames with `rx_id` and sending frames with `tx_id`.
+    pub fn bind<I>(ifname: I, rx_id: Id, tx_id: Id, options: &IsoTpOptions) -> Result<Self>
+    where
+        I: AsRef<CStr>,
+    {
+        let ifindex = if_nametoindex(ifname)?;
+        let socket = Self {
+            fd: socket(libc::SOCK_DGRAM, sys::CAN_ISOTP as _, "CAN_ISOTP")?,
+        };
+        unsafe {
+            socket.setsockopt(sys::CAN_ISOTP_OPTS, &options.opts)?;
+            socket.setsockopt(sys::CAN_ISOTP_RECV_FC, &options.fc)?;
+            if let Some(ll) = &options.ll {
+                socket.setsockopt(sys::CAN_ISOTP_LL_OPTS, ll)?;
+            }
+            if let Some(tx_stmin) = &options.tx_stmin {
+                socket.setsockopt(sys::CAN_ISOTP_TX_STMIN, tx_stmin)?;
+            }
+        }
+
+        let mut address = address(ifindex);
+        address.can_addr.tp = sys::sockaddr_can__bindgen_ty_1__bindgen_ty_1 {
+            rx_id: rx_id.try_into_can_id()?,
+            tx_id: tx_id.try_into_can_id()?,
+        };
+        if unsafe {
+            libc::bind(
+                socket.as_raw_fd(),
+                &address as *const _ as _,
+                size_of_val(&address) as _,
+            ) != 0
+        } {
+            return Err(Error::last_os_error());
+        }
+        Ok(socket)
+    }
+
+    pub fn set_nonblocking(&self, nonblocking: bool) -> Result<()> {
+        if unsafe { libc::ioctl(self.as_raw_fd(), libc::FIONBIO, &(nonblocking as c_int)) } != 0 {
+            return Err(Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    unsafe fn setsockopt<T>(&self, name: u32, value: &T) -> Result<()> {
+        setsockopt(self.as_raw_fd(), sys::SOL_CAN_ISOTP as _, name as _, value)
+    }
+
+    /// Sends a PDU, which is up to 4095 bytes (or larger with CAN FD).
+    pub fn send(&self, pdu: &[u8]) -> Result<()> {
+        if unsafe { libc::write(self.as_raw_fd(), pdu.as_ptr() as _, pdu.len()) } as usize
+            != pdu.len()
+        {
+            return Err(Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Receives a PDU into `buf` and returns its length.
+    /// The PDU is truncated if `buf` is shorter.
+    pub fn recv(&self, buf: &mut [u8]) -> Result<usize> {
+        let size = unsafe { libc::read(self.as_raw_fd(), buf.as_mut_ptr() as _, buf.len()) };
+        if size < 0 {
+            return Err(Error::last_os_error());
+        }
+        Ok(size as _)
+    }
+}
+
+impl Drop for IsoTpSocket {
+    fn drop(&mut self) {
+        unsafe { libc::close(self.as_raw_fd()) };
+    }
+}
+
+impl AsRawFd for IsoTpSocket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd
+    }
+}
+
+impl FromRawFd for IsoTpSocket {
+    unsafe fn from_raw_fd(fd: RawFd) -> Self {
+        Self { fd }
+    }
+}
+
+impl IntoRawFd for IsoTpSocket {
+    fn into_raw_fd(self) -> RawFd {
+        let fd = self.fd;
+        mem::forget(self);
+        fd
+    }
+}
+
+#[cfg(test)]
+mod tests;
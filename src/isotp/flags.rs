@@ -0,0 +1,29 @@
+use crate::sys;
+
+bitflags::bitflags! {
+    /// Flags of ISO-TP sockets.
+    /// Most of them are set by the methods of [`IsoTpOptions`](crate::IsoTpOptions).
+    pub struct IsoTpFlags: u32 {
+        /// Does not send flow control frames.
+        const LISTEN_MODE = sys::CAN_ISOTP_LISTEN_MODE;
+        const EXTEND_ADDR = sys::CAN_ISOTP_EXTEND_ADDR;
+        const TX_PADDING = sys::CAN_ISOTP_TX_PADDING;
+        const RX_PADDING = sys::CAN_ISOTP_RX_PADDING;
+        /// Drops received frames whose length is not padded.
+        const CHK_PAD_LEN = sys::CAN_ISOTP_CHK_PAD_LEN;
+        /// Drops received frames whose padding bytes differ from the content.
+        const CHK_PAD_DATA = sys::CAN_ISOTP_CHK_PAD_DATA;
+        const HALF_DUPLEX = sys::CAN_ISOTP_HALF_DUPLEX;
+        /// Ignores the separation time requested by the receiver.
+        const FORCE_TXSTMIN = sys::CAN_ISOTP_FORCE_TXSTMIN;
+        /// Drops consecutive frames received within the separation time.
+        const FORCE_RXSTMIN = sys::CAN_ISOTP_FORCE_RXSTMIN;
+        const RX_EXT_ADDR = sys::CAN_ISOTP_RX_EXT_ADDR;
+        /// Blocks `send` until the transmission is completed.
+        const WAIT_TX_DONE = sys::CAN_ISOTP_WAIT_TX_DONE;
+        /// Sends single frames only, without waiting for flow control (functional addressing).
+        const SF_BROADCAST = sys::CAN_ISOTP_SF_BROADCAST;
+        /// Sends consecutive frames without waiting for flow control.
+        const CF_BROADCAST = sys::CAN_ISOTP_CF_BROADCAST;
+    }
+}
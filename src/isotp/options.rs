@@ -0,0 +1,155 @@
+use crate::{sys, IsoTpFlags};
+use std::mem::MaybeUninit;
+use std::time::Duration;
+
+/// Options of [`IsoTpSocket`](crate::IsoTpSocket), which must be given at bind time.
+///
+/// ```
+/// use socketcan_alt::IsoTpOptions;
+/// use std::time::Duration;
+///
+/// let options = IsoTpOptions::new()
+///     .tx_padding(0xcc)
+///     .block_size(8)
+///     .stmin(Duration::from_millis(5));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct IsoTpOptions {
+    pub(super) opts: sys::can_isotp_options,
+    pub(super) fc: sys::can_isotp_fc_options,
+    pub(super) ll: Option<sys::can_isotp_ll_options>,
+    pub(super) tx_stmin: Option<u32>,
+}
+
+impl IsoTpOptions {
+    /// Defaults of the kernel: normal addressing without padding,
+    /// and flow control without limits of block size and separation time.
+    pub fn new() -> Self {
+        let mut opts = unsafe { MaybeUninit::<sys::can_isotp_options>::zeroed().assume_init() };
+        opts.txpad_content = sys::CAN_ISOTP_DEFAULT_PAD_CONTENT as _;
+        opts.rxpad_content = sys::CAN_ISOTP_DEFAULT_PAD_CONTENT as _;
+        Self {
+            opts,
+            fc: unsafe { MaybeUninit::zeroed().assume_init() },
+            ll: None,
+            tx_stmin: None,
+        }
+    }
+
+    pub fn flags(mut self, flags: IsoTpFlags) -> Self {
+        self.opts.flags |= flags.bits();
+        self
+    }
+
+    /// Extended addressing, where the first data byte of each frame carries an address.
+    /// The same layout serves mixed addressing.
+    /// `rx` is the address of received frames, which may differ from `tx`.
+    pub fn ext_address(mut self, tx: u8, rx: u8) -> Self {
+        self.opts.flags |= sys::CAN_ISOTP_EXTEND_ADDR;
+        self.opts.ext_address = tx;
+        if rx != tx {
+            self.opts.flags |= sys::CAN_ISOTP_RX_EXT_ADDR;
+            self.opts.rx_ext_address = rx;
+        }
+        self
+    }
+
+    /// Pads sent frames to their full length with `content`.
+    pub fn tx_padding(mut self, content: u8) -> Self {
+        self.opts.flags |= sys::CAN_ISOTP_TX_PADDING;
+        self.opts.txpad_content = content;
+        self
+    }
+
+    /// Expects received frames padded with `content`.
+    /// They are checked only if [`IsoTpFlags::CHK_PAD_LEN`] or [`IsoTpFlags::CHK_PAD_DATA`] is given.
+    pub fn rx_padding(mut self, content: u8) -> Self {
+        self.opts.flags |= sys::CAN_ISOTP_RX_PADDING;
+        self.opts.rxpad_content = content;
+        self
+    }
+
+    /// Does not send flow control frames, e.g. to monitor a transfer between other nodes.
+    pub fn listen_mode(mut self) -> Self {
+        self.opts.flags |= sys::CAN_ISOTP_LISTEN_MODE;
+        self
+    }
+
+    /// Time between sent frames, which the kernel waits in addition to the separation time.
+    /// It is limited to about 4.29 s.
+    pub fn frame_txtime(mut self, frame_txtime: Duration) -> Self {
+        self.opts.frame_txtime = if frame_txtime.is_zero() {
+            sys::CAN_ISOTP_FRAME_TXTIME_ZERO
+        } else {
+            // the maximum, CAN_ISOTP_FRAME_TXTIME_ZERO, stands for zero
+            nanos(frame_txtime).min(sys::CAN_ISOTP_FRAME_TXTIME_ZERO - 1)
+        };
+        self
+    }
+
+    /// Block size requested in flow control frames. Zero means no limit.
+    pub fn block_size(mut self, bs: u8) -> Self {
+        self.fc.bs = bs;
+        self
+    }
+
+    /// Separation time requested in flow control frames.
+    /// It is rounded down to a representable value (100 µs to 900 µs, or 0 ms to 127 ms).
+    pub fn stmin(mut self, stmin: Duration) -> Self {
+        self.fc.stmin = encode_stmin(stmin);
+        self
+    }
+
+    /// Maximum number of wait frames sent in flow control. Zero means no wait frames.
+    pub fn wftmax(mut self, wftmax: u8) -> Self {
+        self.fc.wftmax = wftmax;
+        self
+    }
+
+    /// Uses CAN FD frames with the data length `tx_dl` for sending.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tx_dl` is not 8, 12, 16, 20, 24, 32, 48 or 64.
+    pub fn fd(mut self, tx_dl: u8, brs: bool) -> Self {
+        assert!(
+            matches!(tx_dl, 8 | 12 | 16 | 20 | 24 | 32 | 48 | 64),
+            "invalid data length of CAN FD frames"
+        );
+        self.ll = Some(sys::can_isotp_ll_options {
+            mtu: sys::CANFD_MTU as _,
+            tx_dl,
+            tx_flags: if brs { sys::CANFD_BRS as _ } else { 0 },
+        });
+        self
+    }
+
+    /// Separation time used for sending, regardless of the one requested by the receiver.
+    /// It is limited to about 4.29 s.
+    pub fn force_tx_stmin(mut self, stmin: Duration) -> Self {
+        self.opts.flags |= sys::CAN_ISOTP_FORCE_TXSTMIN;
+        self.tx_stmin = Some(nanos(stmin));
+        self
+    }
+}
+
+impl Default for IsoTpOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// nanoseconds of the kernel options, saturating
+fn nanos(duration: Duration) -> u32 {
+    duration.as_nanos().min(u32::MAX as _) as _
+}
+
+// STmin parameter of flow control frames (ISO 15765-2)
+pub(super) fn encode_stmin(stmin: Duration) -> u8 {
+    let micros = stmin.as_micros();
+    if (100..1000).contains(&micros) {
+        0xf0 + (micros / 100) as u8
+    } else {
+        stmin.as_millis().min(0x7f) as _
+    }
+}
@@ -0,0 +1,157 @@
+use super::options::encode_stmin;
+use super::{IsoTpFlags, IsoTpOptions, IsoTpSocket};
+use crate::socket::tests::{ifname, LOCK};
+use crate::{sys, Id};
+use rand::Rng;
+use std::ffi::CString;
+use std::time::Duration;
+
+macro_rules! lock {
+    (shared) => {
+        let _lock = LOCK.read();
+    };
+    (exclusive) => {
+        let _lock = LOCK.write();
+    };
+}
+
+fn random_pdu(len: usize) -> Vec<u8> {
+    let mut rng = rand::thread_rng();
+    (0..len).map(|_| rng.gen()).collect()
+}
+
+#[test]
+fn test_encode_stmin() {
+    assert_eq!(encode_stmin(Duration::ZERO), 0x00);
+    assert_eq!(encode_stmin(Duration::from_micros(50)), 0x00);
+    assert_eq!(encode_stmin(Duration::from_micros(100)), 0xf1);
+    assert_eq!(encode_stmin(Duration::from_micros(950)), 0xf9);
+    assert_eq!(encode_stmin(Duration::from_millis(1)), 0x01);
+    assert_eq!(encode_stmin(Duration::from_micros(1500)), 0x01);
+    assert_eq!(encode_stmin(Duration::from_millis(127)), 0x7f);
+    assert_eq!(encode_stmin(Duration::from_secs(1)), 0x7f);
+}
+
+#[test]
+fn test_options() {
+    let options = IsoTpOptions::new();
+    assert_eq!(options.opts.flags, 0);
+    assert_eq!(options.opts.txpad_content, 0xcc);
+
+    let options = IsoTpOptions::new().ext_address(0x42, 0x42);
+    assert_eq!(
+        IsoTpFlags::from_bits(options.opts.flags),
+        Some(IsoTpFlags::EXTEND_ADDR)
+    );
+    assert_eq!(options.opts.ext_address, 0x42);
+
+    let options = IsoTpOptions::new().ext_address(0x42, 0x43);
+    assert_eq!(
+        IsoTpFlags::from_bits(options.opts.flags),
+        Some(IsoTpFlags::EXTEND_ADDR | IsoTpFlags::RX_EXT_ADDR)
+    );
+    assert_eq!(options.opts.rx_ext_address, 0x43);
+
+    let options = IsoTpOptions::new().force_tx_stmin(Duration::from_micros(300));
+    assert_eq!(
+        IsoTpFlags::from_bits(options.opts.flags),
+        Some(IsoTpFlags::FORCE_TXSTMIN)
+    );
+    assert_eq!(options.tx_stmin, Some(300_000));
+
+    let options = IsoTpOptions::new().fd(64, true);
+    let ll = options.ll.unwrap();
+    assert_eq!(
+        (ll.mtu as usize, ll.tx_dl, ll.tx_flags as u32),
+        (sys::CANFD_MTU, 64, sys::CANFD_BRS)
+    );
+}
+
+#[test]
+fn test_options_saturate() {
+    let options = IsoTpOptions::new()
+        .frame_txtime(Duration::from_secs(5))
+        .force_tx_stmin(Duration::from_secs(5));
+    assert_eq!(
+        options.opts.frame_txtime,
+        sys::CAN_ISOTP_FRAME_TXTIME_ZERO - 1
+    );
+    assert_eq!(options.tx_stmin, Some(u32::MAX));
+    let options = IsoTpOptions::new().frame_txtime(Duration::ZERO);
+    assert_eq!(options.opts.frame_txtime, sys::CAN_ISOTP_FRAME_TXTIME_ZERO);
+}
+
+#[test]
+#[should_panic]
+fn test_options_fd_invalid() {
+    IsoTpOptions::new().fd(10, false);
+}
+
+#[test]
+#[ignore]
+fn test_bind() {
+    IsoTpSocket::bind(
+        ifname(),
+        Id::Standard(0x7e8),
+        Id::Standard(0x7e0),
+        &IsoTpOptions::new(),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_bind_no_device() {
+    let ifname = CString::new("NO DEVICE").unwrap();
+    assert!(IsoTpSocket::bind(
+        ifname,
+        Id::Standard(0x7e8),
+        Id::Standard(0x7e0),
+        &IsoTpOptions::new()
+    )
+    .is_err());
+}
+
+fn transfer(options: &IsoTpOptions, len: usize) {
+    let a = IsoTpSocket::bind(ifname(), Id::Standard(0x7e8), Id::Standard(0x7e0), options).unwrap();
+    let b = IsoTpSocket::bind(ifname(), Id::Standard(0x7e0), Id::Standard(0x7e8), options).unwrap();
+
+    let pdu = random_pdu(len);
+    a.send(&pdu).unwrap();
+    let mut buf = vec![0; len + 1];
+    let size = b.recv(&mut buf).unwrap();
+    assert_eq!(&buf[..size], pdu);
+}
+
+#[test]
+#[ignore]
+fn test_single_frame() {
+    lock!(shared);
+    transfer(&IsoTpOptions::new(), 7);
+}
+
+#[test]
+#[ignore]
+fn test_multi_frame() {
+    lock!(shared);
+    transfer(
+        &IsoTpOptions::new()
+            .tx_padding(0xaa)
+            .rx_padding(0xaa)
+            .block_size(4),
+        4095,
+    );
+}
+
+#[test]
+#[ignore]
+fn test_ext_address() {
+    lock!(shared);
+    transfer(&IsoTpOptions::new().ext_address(0x42, 0x42), 100);
+}
+
+#[test]
+#[ignore]
+fn test_fd() {
+    lock!(shared);
+    transfer(&IsoTpOptions::new().fd(64, false), 6000);
+}
@@ -0,0 +1,316 @@
+mod address;
+mod event;
+mod filter;
+mod received;
+
+use crate::cmsg::CmsgBuf;
+use crate::socket::{getsockopt, if_nametoindex, setsockopt, socket};
+use crate::{sys, Cmsg, CmsgIter, Error, Result};
+pub use address::J1939Address;
+pub use event::J1939Event;
+pub use filter::J1939Filter;
+pub use received::J1939Received;
+use std::ffi::CStr;
+use std::io;
+use std::mem::{self, size_of, size_of_val, MaybeUninit};
+use std::os::raw::c_int;
+use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use std::ptr;
+
+/// A socket of SAE J1939 (`CAN_J1939`).
+/// The kernel handles address claiming and the transport protocols for messages longer than 8 bytes.
+pub struct J1939Socket {
+    fd: RawFd,
+}
+
+impl J1939Socket {
+    /// Binds a socket to the NAME or the address of the local node.
+    /// With a PGN, only messages of the PGN are received.
+    pub fn bind<I>(ifname: I, address: J1939Address) -> Result<Self>
+    where
+        I: AsRef<CStr>,
+    {
+        let ifindex = if_nametoindex(ifname)?;
+        let socket = Self {
+            fd: socket(libc::SOCK_DGRAM, sys::CAN_J1939 as _, "CAN_J1939")?,
+        };
+
+        let address = address.into_raw(ifindex);
+        if unsafe {
+            libc::bind(
+                socket.as_raw_fd(),
+                &address as *const _ as _,
+                size_of_val(&address) as _,
+            ) != 0
+        } {
+            return Err(Error::last_os_error());
+        }
+        Ok(socket)
+    }
+
+    /// Sets the default destination of [`send`](Self::send),
+    /// and receives messages from the destination only.
+    pub fn connect(&self, address: J1939Address) -> Result<()> {
+        let address = address.into_raw(0);
+        if unsafe {
+            libc::connect(
+                self.as_raw_fd(),
+                &address as *const _ as _,
+                size_of_val(&address) as _,
+            ) != 0
+        } {
+            return Err(Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    pub fn set_nonblocking(&self, nonblocking: bool) -> Result<()> {
+        if unsafe { libc::ioctl(self.as_raw_fd(), libc::FIONBIO, &(nonblocking as c_int)) } != 0 {
+            return Err(Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    fn is_nonblocking(&self) -> Result<bool> {
+        let flags = unsafe { libc::fcntl(self.as_raw_fd(), libc::F_GETFL) };
+        if flags == -1 {
+            return Err(Error::last_os_error());
+        }
+        Ok(flags & libc::O_NONBLOCK != 0)
+    }
+
+    /// Allows sending messages to the broadcast address.
+    pub fn set_broadcast(&self, enable: bool) -> Result<()> {
+        unsafe {
+            setsockopt(
+                self.as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_BROADCAST,
+                &(enable as c_int),
+            )
+        }
+    }
+
+    /// Receives messages regardless of the address and the PGN the socket is bound to.
+    pub fn set_promisc(&self, enable: bool) -> Result<()> {
+        unsafe { self.setsockopt(sys::SO_J1939_PROMISC, &(enable as c_int)) }
+    }
+
+    /// Priority of sent messages, from 0 (highest) to 7 (lowest).
+    pub fn set_send_priority(&self, priority: u8) -> Result<()> {
+        unsafe { self.setsockopt(sys::SO_J1939_SEND_PRIO, &(priority as c_int)) }
+    }
+
+    /// Receives messages matching any of the filters. An empty slice removes the filters.
+    pub fn set_filters(&self, filters: &[J1939Filter]) -> Result<()> {
+        let filters = filters
+            .iter()
+            .map(|&filter| sys::j1939_filter::from(filter))
+            .collect::<Vec<_>>();
+        if unsafe {
+            libc::setsockopt(
+                self.as_raw_fd(),
+                sys::SOL_CAN_J1939 as _,
+                sys::SO_J1939_FILTER as _,
+                filters.as_ptr() as _,
+                (filters.len() * size_of::<sys::j1939_filter>()) as _,
+            )
+        } != 0
+        {
+            return Err(Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Enables notifications of transport protocol sessions, received by [`recv_event`](Self::recv_event).
+    ///
+    /// The notifications are timestamping reports, so this sets or clears their flags in `SO_TIMESTAMPING`
+    /// (including `SOF_TIMESTAMPING_RX_SOFTWARE`, without which no receive notification is reported),
+    /// keeping the other flags set on the socket.
+    pub fn set_errqueue(&self, enable: bool) -> Result<()> {
+        let flags = libc::SOF_TIMESTAMPING_TX_SCHED
+            | libc::SOF_TIMESTAMPING_TX_ACK
+            | libc::SOF_TIMESTAMPING_RX_SOFTWARE
+            | libc::SOF_TIMESTAMPING_OPT_ID
+            | libc::SOF_TIMESTAMPING_OPT_CMSG
+            | libc::SOF_TIMESTAMPING_OPT_TSONLY;
+        unsafe {
+            let mut previous: c_int = 0;
+            getsockopt(
+                self.as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_TIMESTAMPING,
+                &mut previous,
+            )?;
+            let timestamping = if enable {
+                previous | flags as c_int
+            } else {
+                previous & !(flags as c_int)
+            };
+            setsockopt(
+                self.as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_TIMESTAMPING,
+                &timestamping,
+            )?;
+            if let Err(e) = self.setsockopt(sys::SO_J1939_ERRQUEUE, &(enable as c_int)) {
+                // leave the socket as it was
+                let _ = setsockopt(
+                    self.as_raw_fd(),
+                    libc::SOL_SOCKET,
+                    libc::SO_TIMESTAMPING,
+                    &previous,
+                );
+                return Err(e);
+            }
+            Ok(())
+        }
+    }
+
+    unsafe fn setsockopt<T>(&self, name: u32, value: &T) -> Result<()> {
+        setsockopt(self.as_raw_fd(), sys::SOL_CAN_J1939 as _, name as _, value)
+    }
+
+    /// Sends a message to the destination given by [`connect`](Self::connect).
+    pub fn send(&self, data: &[u8]) -> Result<()> {
+        if unsafe { libc::write(self.as_raw_fd(), data.as_ptr() as _, data.len()) } as usize
+            != data.len()
+        {
+            return Err(Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Sends a message with the PGN to the destination. The PGN must be given.
+    pub fn send_to(&self, data: &[u8], address: J1939Address) -> Result<()> {
+        let address = address.into_raw(0);
+        if unsafe {
+            libc::sendto(
+                self.as_raw_fd(),
+                data.as_ptr() as _,
+                data.len(),
+                0,
+                &address as *const _ as _,
+                size_of_val(&address) as _,
+            )
+        } as usize
+            != data.len()
+        {
+            return Err(Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    unsafe fn recvmsg(
+        &self,
+        buf: &mut [u8],
+        cmsg_buf: &mut CmsgBuf,
+        address: *mut sys::sockaddr_can,
+        flags: c_int,
+    ) -> Result<(usize, libc::msghdr)> {
+        let mut iov = libc::iovec {
+            iov_base: buf.as_mut_ptr() as _,
+            iov_len: buf.len(),
+        };
+        let mut msg = MaybeUninit::<libc::msghdr>::zeroed();
+        (*msg.as_mut_ptr()).msg_name = address as _;
+        (*msg.as_mut_ptr()).msg_namelen = if address.is_null() {
+            0
+        } else {
+            size_of::<sys::sockaddr_can>() as _
+        };
+        (*msg.as_mut_ptr()).msg_iov = &mut iov;
+        (*msg.as_mut_ptr()).msg_iovlen = 1;
+        (*msg.as_mut_ptr()).msg_control = cmsg_buf.0.as_mut_ptr() as _;
+        (*msg.as_mut_ptr()).msg_controllen = cmsg_buf.0.len() as _;
+
+        let size = libc::recvmsg(self.as_raw_fd(), msg.as_mut_ptr(), flags);
+        if size < 0 {
+            return Err(Error::last_os_error());
+        }
+        // iov will be dropped
+        (*msg.as_mut_ptr()).msg_iov = ptr::null_mut();
+        Ok((size as _, msg.assume_init()))
+    }
+
+    /// Receives a message into `buf` and returns its length.
+    /// The message is truncated if `buf` is shorter.
+    pub fn recv(&self, buf: &mut [u8]) -> Result<usize> {
+        let size = unsafe { libc::read(self.as_raw_fd(), buf.as_mut_ptr() as _, buf.len()) };
+        if size < 0 {
+            return Err(Error::last_os_error());
+        }
+        Ok(size as _)
+    }
+
+    /// Receives a message into `buf` and returns its length with the metadata.
+    pub fn recv_from(&self, buf: &mut [u8]) -> Result<(usize, J1939Received)> {
+        let mut cmsg_buf = CmsgBuf::new();
+        let mut address = MaybeUninit::<sys::sockaddr_can>::zeroed();
+        unsafe {
+            let (size, msg) = self.recvmsg(buf, &mut cmsg_buf, address.as_mut_ptr(), 0)?;
+            let cmsgs = CmsgIter::from_raw(msg).ok_or(Error::TruncatedControlData)?;
+            Ok((size, J1939Received::new(&address.assume_init(), cmsgs)))
+        }
+    }
+
+    /// Receives a notification enabled by [`set_errqueue`](Self::set_errqueue).
+    pub fn recv_event(&self) -> Result<J1939Event> {
+        // recvmsg does not block on the error queue, which is signaled by POLLERR
+        if !self.is_nonblocking()? {
+            let mut pollfd = libc::pollfd {
+                fd: self.as_raw_fd(),
+                events: 0,
+                revents: 0,
+            };
+            if unsafe { libc::poll(&mut pollfd, 1, -1) } == -1 {
+                return Err(Error::last_os_error());
+            }
+        }
+
+        let mut cmsg_buf = CmsgBuf::new();
+        let cmsgs = unsafe {
+            let (_, msg) =
+                self.recvmsg(&mut [], &mut cmsg_buf, ptr::null_mut(), libc::MSG_ERRQUEUE)?;
+            CmsgIter::from_raw(msg).ok_or(Error::TruncatedControlData)?
+        };
+        cmsgs
+            .filter_map(|cmsg| match cmsg {
+                Cmsg::J1939Errqueue(err) => J1939Event::from_raw(err),
+                _ => None,
+            })
+            .next()
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "unknown J1939 notification").into()
+            })
+    }
+}
+
+impl Drop for J1939Socket {
+    fn drop(&mut self) {
+        unsafe { libc::close(self.as_raw_fd()) };
+    }
+}
+
+impl AsRawFd for J1939Socket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd
+    }
+}
+
+impl FromRawFd for J1939Socket {
+    unsafe fn from_raw_fd(fd: RawFd) -> Self {
+        Self { fd }
+    }
+}
+
+impl IntoRawFd for J1939Socket {
+    fn into_raw_fd(self) -> RawFd {
+        let fd = self.fd;
+        mem::forget(self);
+        fd
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,33 @@
+use crate::socket::address;
+use crate::sys;
+use std::os::raw::c_uint;
+
+/// Address of a J1939 socket: the NAME and the address of a node, and a PGN.
+/// `None` stands for no NAME, no PGN or no address (i.e. broadcast as a destination) respectively.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct J1939Address {
+    pub name: Option<u64>,
+    pub pgn: Option<u32>,
+    pub addr: Option<u8>,
+}
+
+impl J1939Address {
+    pub(super) fn from_raw(address: &sys::sockaddr_can) -> Self {
+        let j1939 = unsafe { address.can_addr.j1939 };
+        Self {
+            name: Some(j1939.name).filter(|&name| name != sys::J1939_NO_NAME as _),
+            pgn: Some(j1939.pgn).filter(|&pgn| pgn != sys::J1939_NO_PGN),
+            addr: Some(j1939.addr).filter(|&addr| addr != sys::J1939_NO_ADDR as _),
+        }
+    }
+
+    pub(super) fn into_raw(self, ifindex: c_uint) -> sys::sockaddr_can {
+        let mut address = address(ifindex);
+        address.can_addr.j1939 = sys::sockaddr_can__bindgen_ty_1__bindgen_ty_2 {
+            name: self.name.unwrap_or(sys::J1939_NO_NAME as _),
+            pgn: self.pgn.unwrap_or(sys::J1939_NO_PGN),
+            addr: self.addr.unwrap_or(sys::J1939_NO_ADDR as _),
+        };
+        address
+    }
+}
@@ -0,0 +1,46 @@
+use crate::sys;
+
+/// A notification of a transport protocol session, enabled by `set_errqueue`.
+///
+/// `id` of transmissions counts the messages sent by the socket, starting from zero.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum J1939Event {
+    /// A transmission started.
+    TxScheduled { id: u32 },
+    /// A transmission completed.
+    /// Messages sent with a transport protocol are acknowledged by the receiver.
+    TxCompleted { id: u32 },
+    /// A transmission was aborted with the reason as `errno`.
+    TxAborted { id: u32, errno: i32 },
+    /// A session to receive a message started (RTS or BAM).
+    RxStarted,
+    /// A data packet offset of an extended transport protocol session was received.
+    RxDataPacketOffset,
+    /// Receiving a message was aborted with the reason as `errno`.
+    RxAborted { errno: i32 },
+}
+
+impl J1939Event {
+    pub(super) fn from_raw(err: &libc::sock_extended_err) -> Option<Self> {
+        let id = err.ee_data;
+        match (err.ee_origin, err.ee_info) {
+            (libc::SO_EE_ORIGIN_TIMESTAMPING, sys::SCM_TSTAMP_SCHED) => {
+                Some(Self::TxScheduled { id })
+            }
+            (libc::SO_EE_ORIGIN_TIMESTAMPING, sys::SCM_TSTAMP_ACK) => {
+                Some(Self::TxCompleted { id })
+            }
+            (libc::SO_EE_ORIGIN_LOCAL, sys::J1939_EE_INFO_TX_ABORT) => Some(Self::TxAborted {
+                id,
+                errno: err.ee_errno as _,
+            }),
+            (libc::SO_EE_ORIGIN_LOCAL, sys::J1939_EE_INFO_RX_RTS) => Some(Self::RxStarted),
+            (libc::SO_EE_ORIGIN_LOCAL, sys::J1939_EE_INFO_RX_DPO) => Some(Self::RxDataPacketOffset),
+            (libc::SO_EE_ORIGIN_LOCAL, sys::J1939_EE_INFO_RX_ABORT) => Some(Self::RxAborted {
+                errno: err.ee_errno as _,
+            }),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use crate::sys;
+
+/// A filter of received messages, which matches if the masked fields are equal.
+/// Zero masks match any value.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct J1939Filter {
+    pub name: u64,
+    pub name_mask: u64,
+    pub pgn: u32,
+    pub pgn_mask: u32,
+    pub addr: u8,
+    pub addr_mask: u8,
+}
+
+impl From<J1939Filter> for sys::j1939_filter {
+    fn from(filter: J1939Filter) -> Self {
+        Self {
+            name: filter.name,
+            name_mask: filter.name_mask,
+            pgn: filter.pgn,
+            pgn_mask: filter.pgn_mask,
+            addr: filter.addr,
+            addr_mask: filter.addr_mask,
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use super::J1939Address;
+use crate::{sys, Cmsg, CmsgIter};
+
+/// The metadata of a received J1939 message.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct J1939Received {
+    /// NAME and address of the sender, and the PGN of the message.
+    pub source: J1939Address,
+    /// `None` if the message was broadcast.
+    pub destination: Option<u8>,
+    pub destination_name: Option<u64>,
+    pub priority: Option<u8>,
+}
+
+impl J1939Received {
+    pub(super) fn new(address: &sys::sockaddr_can, cmsgs: CmsgIter<'_>) -> Self {
+        let mut received = Self {
+            source: J1939Address::from_raw(address),
+            destination: None,
+            destination_name: None,
+            priority: None,
+        };
+        for cmsg in cmsgs {
+            match cmsg {
+                Cmsg::J1939DestAddr(&addr) => {
+                    received.destination =
+                        Some(addr).filter(|&addr| addr != sys::J1939_NO_ADDR as _)
+                }
+                Cmsg::J1939DestName(&name) => received.destination_name = Some(name),
+                Cmsg::J1939Prio(&priority) => received.priority = Some(priority),
+                _ => (),
+            }
+        }
+        received
+    }
+}
@@ -0,0 +1,315 @@
+use super::{J1939Address, J1939Event, J1939Filter, J1939Socket};
+use crate::socket::tests::{ifname, timeout, LOCK};
+use crate::socket::{getsockopt, setsockopt};
+use crate::sys;
+use std::ffi::CString;
+use std::mem::MaybeUninit;
+use std::os::unix::io::AsRawFd;
+
+macro_rules! lock {
+    (shared) => {
+        let _lock = LOCK.read();
+    };
+    (exclusive) => {
+        let _lock = LOCK.write();
+    };
+}
+
+fn sock_extended_err(origin: u8, info: u32, errno: u32, data: u32) -> libc::sock_extended_err {
+    let mut err = unsafe { MaybeUninit::<libc::sock_extended_err>::zeroed().assume_init() };
+    err.ee_origin = origin;
+    err.ee_info = info;
+    err.ee_errno = errno;
+    err.ee_data = data;
+    err
+}
+
+#[test]
+fn test_address() {
+    let address = J1939Address {
+        name: Some(0x1234_5678_9abc_def0),
+        pgn: Some(0xfeca),
+        addr: Some(0x20),
+    };
+    let raw = address.into_raw(42);
+    assert_eq!(raw.can_ifindex, 42);
+    assert_eq!(J1939Address::from_raw(&raw), address);
+
+    let raw = J1939Address::default().into_raw(0);
+    let j1939 = unsafe { raw.can_addr.j1939 };
+    assert_eq!(j1939.name, sys::J1939_NO_NAME as u64);
+    assert_eq!(j1939.pgn, sys::J1939_NO_PGN);
+    assert_eq!(j1939.addr, sys::J1939_NO_ADDR as u8);
+    assert_eq!(J1939Address::from_raw(&raw), J1939Address::default());
+}
+
+#[test]
+fn test_filter() {
+    let filter = sys::j1939_filter::from(J1939Filter {
+        pgn: 0xfeca,
+        pgn_mask: sys::J1939_PGN_MAX,
+        ..J1939Filter::default()
+    });
+    assert_eq!((filter.pgn, filter.pgn_mask), (0xfeca, sys::J1939_PGN_MAX));
+    assert_eq!((filter.name_mask, filter.addr_mask), (0, 0));
+}
+
+#[test]
+fn test_event() {
+    assert_eq!(
+        J1939Event::from_raw(&sock_extended_err(
+            libc::SO_EE_ORIGIN_TIMESTAMPING,
+            sys::SCM_TSTAMP_SCHED,
+            libc::ENOMSG as _,
+            3
+        )),
+        Some(J1939Event::TxScheduled { id: 3 })
+    );
+    assert_eq!(
+        J1939Event::from_raw(&sock_extended_err(
+            libc::SO_EE_ORIGIN_TIMESTAMPING,
+            sys::SCM_TSTAMP_ACK,
+            libc::ENOMSG as _,
+            3
+        )),
+        Some(J1939Event::TxCompleted { id: 3 })
+    );
+    assert_eq!(
+        J1939Event::from_raw(&sock_extended_err(
+            libc::SO_EE_ORIGIN_LOCAL,
+            sys::J1939_EE_INFO_TX_ABORT,
+            libc::ETIME as _,
+            4
+        )),
+        Some(J1939Event::TxAborted {
+            id: 4,
+            errno: libc::ETIME
+        })
+    );
+    assert_eq!(
+        J1939Event::from_raw(&sock_extended_err(
+            libc::SO_EE_ORIGIN_LOCAL,
+            sys::J1939_EE_INFO_RX_RTS,
+            libc::ENOMSG as _,
+            0
+        )),
+        Some(J1939Event::RxStarted)
+    );
+    assert_eq!(
+        J1939Event::from_raw(&sock_extended_err(
+            libc::SO_EE_ORIGIN_LOCAL,
+            sys::J1939_EE_INFO_NONE,
+            0,
+            0
+        )),
+        None
+    );
+}
+
+#[test]
+#[ignore]
+fn test_bind() {
+    J1939Socket::bind(
+        ifname(),
+        J1939Address {
+            addr: Some(0x20),
+            ..J1939Address::default()
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_bind_no_device() {
+    let ifname = CString::new("NO DEVICE").unwrap();
+    assert!(J1939Socket::bind(ifname, J1939Address::default()).is_err());
+}
+
+fn pair() -> (J1939Socket, J1939Socket) {
+    let a = J1939Socket::bind(
+        ifname(),
+        J1939Address {
+            addr: Some(0x20),
+            ..J1939Address::default()
+        },
+    )
+    .unwrap();
+    let b = J1939Socket::bind(
+        ifname(),
+        J1939Address {
+            addr: Some(0x30),
+            ..J1939Address::default()
+        },
+    )
+    .unwrap();
+    (a, b)
+}
+
+#[test]
+#[ignore]
+fn test_send_to() {
+    lock!(exclusive);
+    let (a, b) = pair();
+
+    a.set_send_priority(3).unwrap();
+    a.send_to(
+        &[0, 1, 2, 3],
+        J1939Address {
+            pgn: Some(0x12300),
+            addr: Some(0x30),
+            ..J1939Address::default()
+        },
+    )
+    .unwrap();
+    let (data, received) = timeout(move || {
+        let mut buf = [0; 16];
+        let (size, received) = b.recv_from(&mut buf).unwrap();
+        (buf[..size].to_vec(), received)
+    })
+    .unwrap();
+    assert_eq!(data, [0, 1, 2, 3]);
+    assert_eq!(received.source.addr, Some(0x20));
+    assert_eq!(received.source.pgn, Some(0x12300));
+    assert_eq!(received.destination, Some(0x30));
+    assert_eq!(received.priority, Some(3));
+}
+
+#[test]
+#[ignore]
+fn test_broadcast() {
+    lock!(exclusive);
+    let (a, b) = pair();
+
+    let address = J1939Address {
+        pgn: Some(0xfeca),
+        ..J1939Address::default()
+    };
+    assert!(a.send_to(&[0; 8], address).is_err());
+    a.set_broadcast(true).unwrap();
+    b.set_broadcast(true).unwrap();
+    a.send_to(&[0; 8], address).unwrap();
+    let received = timeout(move || b.recv_from(&mut [0; 16]).unwrap().1).unwrap();
+    assert_eq!(received.destination, None);
+}
+
+#[test]
+#[ignore]
+fn test_filters() {
+    lock!(exclusive);
+    let (a, b) = pair();
+
+    b.set_filters(&[J1939Filter {
+        pgn: 0x12300,
+        pgn_mask: sys::J1939_PGN_PDU1_MAX,
+        ..J1939Filter::default()
+    }])
+    .unwrap();
+    for pgn in [0x45600, 0x12300] {
+        a.send_to(
+            &[0; 8],
+            J1939Address {
+                pgn: Some(pgn),
+                addr: Some(0x30),
+                ..J1939Address::default()
+            },
+        )
+        .unwrap();
+    }
+    let received = timeout(move || b.recv_from(&mut [0; 16]).unwrap().1).unwrap();
+    assert_eq!(received.source.pgn, Some(0x12300));
+}
+
+#[test]
+#[ignore]
+fn test_errqueue() {
+    lock!(exclusive);
+    let (a, b) = pair();
+
+    a.set_errqueue(true).unwrap();
+    // transport protocol
+    a.send_to(
+        &[0; 100],
+        J1939Address {
+            pgn: Some(0x12300),
+            addr: Some(0x30),
+            ..J1939Address::default()
+        },
+    )
+    .unwrap();
+    let events = timeout(move || {
+        let mut buf = [0; 128];
+        assert_eq!(b.recv(&mut buf).unwrap(), 100);
+        [a.recv_event().unwrap(), a.recv_event().unwrap()]
+    })
+    .unwrap();
+    assert_eq!(
+        events,
+        [
+            J1939Event::TxScheduled { id: 0 },
+            J1939Event::TxCompleted { id: 0 }
+        ]
+    );
+}
+
+#[test]
+#[ignore]
+fn test_errqueue_rx() {
+    lock!(exclusive);
+    let (a, b) = pair();
+
+    b.set_errqueue(true).unwrap();
+    // transport protocol
+    a.send_to(
+        &[0; 100],
+        J1939Address {
+            pgn: Some(0x12300),
+            addr: Some(0x30),
+            ..J1939Address::default()
+        },
+    )
+    .unwrap();
+    let event = timeout(move || {
+        let mut buf = [0; 128];
+        assert_eq!(b.recv(&mut buf).unwrap(), 100);
+        b.recv_event().unwrap()
+    })
+    .unwrap();
+    assert_eq!(event, J1939Event::RxStarted);
+}
+
+#[test]
+#[ignore]
+fn test_errqueue_keeps_timestamping() {
+    lock!(exclusive);
+    let (a, _) = pair();
+    let timestamping = |socket: &J1939Socket| {
+        let mut value: libc::c_int = 0;
+        unsafe {
+            getsockopt(
+                socket.as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_TIMESTAMPING,
+                &mut value,
+            )
+            .unwrap()
+        };
+        value as u32
+    };
+    let rx = libc::SOF_TIMESTAMPING_RX_HARDWARE | libc::SOF_TIMESTAMPING_RAW_HARDWARE;
+    unsafe {
+        setsockopt(
+            a.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_TIMESTAMPING,
+            &(rx as libc::c_int),
+        )
+        .unwrap()
+    };
+
+    a.set_errqueue(true).unwrap();
+    assert_eq!(timestamping(&a) & rx, rx);
+    assert_ne!(timestamping(&a) & libc::SOF_TIMESTAMPING_TX_ACK, 0);
+    assert_ne!(timestamping(&a) & libc::SOF_TIMESTAMPING_RX_SOFTWARE, 0);
+    a.set_errqueue(false).unwrap();
+    assert_eq!(timestamping(&a), rx);
+}
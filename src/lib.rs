@@ -20,13 +20,53 @@
 
 #[cfg(feature = "aio")]
 pub mod aio;
+mod bcm;
+mod bitstream;
 mod cmsg;
+mod error;
 mod frame;
+mod gw;
+mod interface;
+mod isotp;
+mod j1939;
+mod link;
+mod msg_flags;
+mod netlink;
+mod received_frame;
+mod reconnecting_socket;
 mod socket;
+mod stats;
 mod sys;
 mod timestamping;
+mod virtual_interface;
 
+pub use bcm::{BcmFlags, BcmMessage, BcmSocket, RxSetup, TxSetup};
+pub use bitstream::Bitstream;
 pub use cmsg::{Cmsg, CmsgIter};
+pub use error::{Error, Result};
 pub use frame::*;
+pub use gw::{
+    CanGw, GwCrc8Checksum, GwCrc8Profile, GwEntry, GwFilter, GwFlags, GwModification, GwOp, GwRule,
+    GwTargets, GwXorChecksum,
+};
+pub use interface::{
+    list_interfaces, Interface, InterfaceEvent, InterfaceFlags, InterfaceWatcher, LinkKind,
+    MtuClass, OperState,
+};
+pub use isotp::{IsoTpFlags, IsoTpOptions, IsoTpSocket};
+pub use j1939::{J1939Address, J1939Event, J1939Filter, J1939Received, J1939Socket};
+pub use link::{
+    BerrCounter, BitTiming, BitTimingConst, CalculatedBitTiming, CanLink, CanState, CtrlMode,
+    DeviceStats, LinkCapabilities, LinkConfig, LinkEvent, LinkMonitor, LinkStatus, Tdc, TdcConst,
+    TdcMode,
+};
+pub use msg_flags::MsgFlags;
+pub use received_frame::ReceivedFrame;
+pub use reconnecting_socket::{ReconnectingSocket, SocketEvent};
 pub use socket::Socket;
+pub use stats::{
+    can_stats, link_stats, receive_list, CanRates, CanStats, LinkStats, ReceiveList,
+    ReceiveListEntry,
+};
 pub use timestamping::Timestamping;
+pub use virtual_interface::VirtualInterface;
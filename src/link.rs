@@ -0,0 +1,268 @@
+mod bittiming;
+mod bittiming_const;
+mod calc;
+mod capabilities;
+mod config;
+mod ctrlmode;
+mod monitor;
+mod status;
+mod tdc;
+
+use crate::netlink::{Attrs, Message, MessageBuilder, Netlink};
+use crate::socket::if_nametoindex;
+use crate::{sys, Error, MtuClass, Result};
+pub use bittiming::BitTiming;
+pub use bittiming_const::BitTimingConst;
+pub use calc::CalculatedBitTiming;
+pub use capabilities::LinkCapabilities;
+pub use config::LinkConfig;
+pub use ctrlmode::CtrlMode;
+pub use monitor::{LinkEvent, LinkMonitor};
+pub use status::{BerrCounter, CanState, DeviceStats, LinkStatus};
+use std::ffi::CStr;
+use std::io;
+use std::mem::MaybeUninit;
+pub use tdc::{Tdc, TdcConst, TdcMode};
+
+/// A CAN interface configured via rtnetlink, like `ip link set can0 type can ...`.
+/// Changing settings requires `CAP_NET_ADMIN`.
+///
+/// Settings rejected by the driver fail with [`Error::Netlink`](crate::Error::Netlink),
+/// whose `errno` is e.g. `EBUSY` when the interface is up,
+/// `EOPNOTSUPP` when the controller does not support a control mode,
+/// and `EINVAL` or `ERANGE` when bit timing cannot be satisfied.
+/// The kernel explains the rejection in `message` on Linux 4.12 or later for some errors.
+pub struct CanLink {
+    ifindex: u32,
+    netlink: Netlink,
+}
+
+impl CanLink {
+    pub fn new<I>(ifname: I) -> Result<Self>
+    where
+        I: AsRef<CStr>,
+    {
+        Ok(Self {
+            ifindex: if_nametoindex(ifname)?,
+            netlink: Netlink::route()?,
+        })
+    }
+
+    pub fn ifindex(&self) -> u32 {
+        self.ifindex
+    }
+
+    pub fn status(&self) -> Result<LinkStatus> {
+        self.netlink
+            .request(getlink(self.ifindex))?
+            .iter()
+            .filter(|message| message.ty() == sys::RTM_NEWLINK)
+            .find_map(status::decode)
+            .ok_or_else(|| unsupported("the interface is not a CAN interface"))
+    }
+
+    pub fn capabilities(&self) -> Result<LinkCapabilities> {
+        self.netlink
+            .request(getlink(self.ifindex))?
+            .iter()
+            .filter(|message| message.ty() == sys::RTM_NEWLINK)
+            .find_map(capabilities::decode)
+            .ok_or_else(|| unsupported("the interface is not a CAN interface"))
+    }
+
+    /// Fails with [`io::ErrorKind::Unsupported`] for interfaces without a controller.
+    pub fn state(&self) -> Result<CanState> {
+        self.status()?
+            .state
+            .ok_or_else(|| unsupported("the interface does not report the controller state"))
+    }
+
+    /// Fails with [`io::ErrorKind::Unsupported`] if the driver does not report the error counters.
+    pub fn berr_counter(&self) -> Result<BerrCounter> {
+        self.status()?
+            .berr_counter
+            .ok_or_else(|| unsupported("the driver does not report the error counters"))
+    }
+
+    /// The termination and TDC are validated against [`CanLink::capabilities`] if given,
+    /// and TDC against the other settings, failing with [`Error::InvalidLinkConfig`].
+    pub fn configure(&self, config: &LinkConfig) -> Result<()> {
+        if config.termination.is_some() || config.tdc.is_some() {
+            validate(config, &self.capabilities()?)?;
+        }
+        self.netlink.request(encode(self.ifindex, config))?;
+        Ok(())
+    }
+
+    /// Returns the termination resistance in Ohm,
+    /// or `None` if the controller does not have a switchable termination.
+    pub fn termination(&self) -> Result<Option<u16>> {
+        Ok(self.status()?.termination)
+    }
+
+    /// Returns the transmitter delay compensation, or `None` if disabled.
+    pub fn tdc(&self) -> Result<Option<Tdc>> {
+        Ok(self.status()?.tdc)
+    }
+
+    /// Restarts the controller from bus-off manually.
+    pub fn restart(&self) -> Result<()> {
+        let mut message = newlink(self.ifindex, 0, 0);
+        linkinfo(&mut message, |message| {
+            message.attr_value(sys::IFLA_CAN_RESTART, &1_u32);
+        });
+        self.netlink.request(message)?;
+        Ok(())
+    }
+
+    pub fn mtu_class(&self) -> Result<MtuClass> {
+        Ok(self.status()?.interface.mtu_class)
+    }
+
+    /// Sets the MTU to [`MtuClass::mtu`], like `ip link set can0 mtu 72`.
+    /// Most drivers require the interface to be down, failing with `EBUSY` otherwise.
+    ///
+    /// Sockets bound to the interface pick up the change on [`Socket::refresh_mtu`](crate::Socket::refresh_mtu).
+    pub fn set_mtu_class(&self, mtu: MtuClass) -> Result<()> {
+        self.netlink.request(set_mtu(self.ifindex, mtu))?;
+        Ok(())
+    }
+
+    /// Brings the interface up or down, like `ip link set can0 up`.
+    pub fn set_up(&self, up: bool) -> Result<()> {
+        let flags = if up { libc::IFF_UP as u32 } else { 0 };
+        self.netlink
+            .request(newlink(self.ifindex, flags, libc::IFF_UP as u32))?;
+        Ok(())
+    }
+}
+
+fn unsupported(message: &'static str) -> Error {
+    io::Error::new(io::ErrorKind::Unsupported, message).into()
+}
+
+fn ifinfomsg(ifindex: u32) -> sys::ifinfomsg {
+    let mut header = unsafe { MaybeUninit::<sys::ifinfomsg>::zeroed().assume_init() };
+    header.ifi_family = libc::AF_UNSPEC as _;
+    header.ifi_index = ifindex as _;
+    header
+}
+
+fn getlink(ifindex: u32) -> MessageBuilder {
+    MessageBuilder::new(sys::RTM_GETLINK, 0, &ifinfomsg(ifindex))
+}
+
+fn newlink(ifindex: u32, flags: u32, change: u32) -> MessageBuilder {
+    let mut header = ifinfomsg(ifindex);
+    header.ifi_flags = flags;
+    header.ifi_change = change;
+    MessageBuilder::new(sys::RTM_NEWLINK, 0, &header)
+}
+
+fn set_mtu(ifindex: u32, mtu: MtuClass) -> MessageBuilder {
+    let mut message = newlink(ifindex, 0, 0);
+    message.attr_value(sys::IFLA_MTU, &(mtu.mtu() as u32));
+    message
+}
+
+// IFLA_LINKINFO { IFLA_INFO_KIND "can", IFLA_INFO_DATA { f } }
+fn linkinfo<F>(message: &mut MessageBuilder, f: F)
+where
+    F: FnOnce(&mut MessageBuilder),
+{
+    message.nest(sys::IFLA_LINKINFO, |message| {
+        message
+            .attr_string(sys::IFLA_INFO_KIND, "can")
+            .nest(sys::IFLA_INFO_DATA, f);
+    });
+}
+
+// attributes in IFLA_LINKINFO
+fn linkinfo_attrs(message: &Message) -> impl Iterator<Item = (u32, &[u8])> {
+    message
+        .attrs::<sys::ifinfomsg>()
+        .filter(|(ty, _)| *ty == sys::IFLA_LINKINFO)
+        .flat_map(|(_, payload)| Attrs(payload))
+}
+
+// attributes in IFLA_LINKINFO/IFLA_INFO_DATA
+fn info_data_attrs(message: &Message) -> impl Iterator<Item = (u32, &[u8])> {
+    linkinfo_attrs(message)
+        .filter(|(ty, _)| *ty == sys::IFLA_INFO_DATA)
+        .flat_map(|(_, payload)| Attrs(payload))
+}
+
+fn validate(config: &LinkConfig, capabilities: &LinkCapabilities) -> Result<()> {
+    if let Some(termination) = config.termination {
+        if !capabilities.terminations.contains(&termination) {
+            return Err(Error::InvalidLinkConfig {
+                reason: "the termination is not supported by the controller",
+            });
+        }
+    }
+    if matches!(config.tdc, Some(TdcMode::Auto { .. } | TdcMode::Manual(_))) {
+        let fd = config
+            .ctrlmode
+            .is_some_and(|ctrlmode| ctrlmode.flags & CtrlMode::FD.bits() != 0);
+        if config.data_bittiming.is_none() || !fd {
+            return Err(Error::InvalidLinkConfig {
+                reason: "TDC requires the data bit timing and CAN FD to be given",
+            });
+        }
+    }
+    match (&config.tdc, &capabilities.tdc_const) {
+        (None, _) | (Some(TdcMode::Off), _) => (),
+        (Some(_), None) => {
+            return Err(Error::InvalidLinkConfig {
+                reason: "TDC is not supported by the controller",
+            })
+        }
+        (Some(mode), Some(tdc_const)) => {
+            if !tdc_const.contains(mode) {
+                return Err(Error::InvalidLinkConfig {
+                    reason: "TDC parameters exceed the limits of the controller",
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+fn encode(ifindex: u32, config: &LinkConfig) -> MessageBuilder {
+    let mut message = newlink(ifindex, 0, 0);
+    linkinfo(&mut message, |message| {
+        if let Some(bittiming) = &config.bittiming {
+            message.attr_value(sys::IFLA_CAN_BITTIMING, bittiming);
+        }
+        if let Some(bittiming) = &config.data_bittiming {
+            message.attr_value(sys::IFLA_CAN_DATA_BITTIMING, bittiming);
+        }
+        if let Some(ctrlmode) = &config.ctrlmode {
+            message.attr_value(sys::IFLA_CAN_CTRLMODE, ctrlmode);
+        }
+        if let Some(restart_ms) = &config.restart_ms {
+            message.attr_value(sys::IFLA_CAN_RESTART_MS, restart_ms);
+        }
+        if let Some(termination) = &config.termination {
+            message.attr_value(sys::IFLA_CAN_TERMINATION, termination);
+        }
+        let (tdcv, tdco, tdcf) = match config.tdc {
+            None | Some(TdcMode::Off) => return,
+            Some(TdcMode::Auto { tdco, tdcf }) => (None, tdco, tdcf),
+            Some(TdcMode::Manual(tdc)) => (Some(tdc.tdcv), tdc.tdco, tdc.tdcf),
+        };
+        message.nest(sys::IFLA_CAN_TDC, |message| {
+            if let Some(tdcv) = &tdcv {
+                message.attr_value(sys::IFLA_CAN_TDC_TDCV, tdcv);
+            }
+            message.attr_value(sys::IFLA_CAN_TDC_TDCO, &tdco);
+            if tdcf != 0 {
+                message.attr_value(sys::IFLA_CAN_TDC_TDCF, &tdcf);
+            }
+        });
+    });
+    message
+}
+
+#[cfg(test)]
+mod tests;
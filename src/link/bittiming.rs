@@ -0,0 +1,61 @@
+use crate::sys;
+
+/// Bit timing parameters of a CAN controller (`struct can_bittiming`).
+///
+/// When configuring, either `bitrate` or `tq` is given and the kernel calculates the rest.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BitTiming {
+    /// Bitrate in bit/s.
+    pub bitrate: u32,
+    /// Sample point in one-tenth of a percent, e.g. 875 for 87.5%.
+    pub sample_point: u32,
+    /// Time quantum in nanoseconds.
+    pub tq: u32,
+    /// Propagation segment in time quanta.
+    pub prop_seg: u32,
+    /// Phase buffer segment 1 in time quanta.
+    pub phase_seg1: u32,
+    /// Phase buffer segment 2 in time quanta.
+    pub phase_seg2: u32,
+    /// Synchronisation jump width in time quanta.
+    pub sjw: u32,
+    /// Bitrate prescaler.
+    pub brp: u32,
+}
+
+impl BitTiming {
+    /// The kernel chooses the sample point recommended by CiA if `sample_point` is `None`.
+    /// Requires `CONFIG_CAN_CALC_BITTIMING` unless the controller supports fixed bitrates only.
+    pub fn from_bitrate(bitrate: u32, sample_point: Option<u32>) -> Self {
+        Self {
+            bitrate,
+            sample_point: sample_point.unwrap_or(0),
+            ..Self::default()
+        }
+    }
+
+    /// The kernel calculates the prescaler from the time quantum in nanoseconds.
+    pub fn from_tq(tq: u32, prop_seg: u32, phase_seg1: u32, phase_seg2: u32, sjw: u32) -> Self {
+        Self {
+            tq,
+            prop_seg,
+            phase_seg1,
+            phase_seg2,
+            sjw,
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn into_raw(self) -> sys::can_bittiming {
+        sys::can_bittiming {
+            bitrate: self.bitrate,
+            sample_point: self.sample_point,
+            tq: self.tq,
+            prop_seg: self.prop_seg,
+            phase_seg1: self.phase_seg1,
+            phase_seg2: self.phase_seg2,
+            sjw: self.sjw,
+            brp: self.brp,
+        }
+    }
+}
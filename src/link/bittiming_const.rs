@@ -0,0 +1,36 @@
+use crate::netlink::read_string;
+use crate::sys;
+
+/// Limits of the bit timing of a CAN controller (`struct can_bittiming_const`), in time quanta.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BitTimingConst {
+    /// Name of the controller.
+    pub name: String,
+    /// Limits of `prop_seg + phase_seg1`.
+    pub tseg1_min: u32,
+    pub tseg1_max: u32,
+    /// Limits of `phase_seg2`.
+    pub tseg2_min: u32,
+    pub tseg2_max: u32,
+    pub sjw_max: u32,
+    pub brp_min: u32,
+    pub brp_max: u32,
+    /// Step of the bitrate prescaler.
+    pub brp_inc: u32,
+}
+
+impl BitTimingConst {
+    pub(crate) fn from_raw(btc: sys::can_bittiming_const) -> Self {
+        Self {
+            name: read_string(&btc.name.map(|c| c as u8)),
+            tseg1_min: btc.tseg1_min,
+            tseg1_max: btc.tseg1_max,
+            tseg2_min: btc.tseg2_min,
+            tseg2_max: btc.tseg2_max,
+            sjw_max: btc.sjw_max,
+            brp_min: btc.brp_min,
+            brp_max: btc.brp_max,
+            brp_inc: btc.brp_inc,
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use crate::{BitTiming, BitTimingConst, Error, Result};
+
+pub(super) const SYNC_SEG: u32 = 1;
+// in one-tenth of a percent
+const MAX_ERROR: u64 = 50;
+
+/// A result of [`BitTiming::calculate`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CalculatedBitTiming {
+    /// Bit timing with the achieved bitrate and sample point.
+    pub bittiming: BitTiming,
+    /// Difference between the requested and the achieved bitrate in bit/s.
+    pub bitrate_error: u32,
+    /// Sample point requested, or recommended by CiA if not given.
+    pub nominal_sample_point: u32,
+    /// Difference between the nominal and the achieved sample point
+    /// in one-tenth of a percent.
+    pub sample_point_error: u32,
+}
+
+impl BitTiming {
+    /// Calculates the bit timing for a controller as `can_calc_bittiming` of the kernel (Linux 6.3 or later),
+    /// so that the result equals the bit timing the kernel configures with
+    /// [`BitTiming::from_bitrate`].
+    /// `clock` is the frequency of the controller in Hz (`struct can_clock`).
+    ///
+    /// The sample point recommended by CiA is used if `sample_point` is `None`:
+    /// 75.0% above 800 kbit/s, 80.0% above 500 kbit/s and 87.5% otherwise.
+    /// It applies to both the nominal and the data bit timing.
+    ///
+    /// Fails with [`Error::InvalidBitTiming`] if the bitrate error exceeds 5.0%,
+    /// as the kernel rejects the bitrate.
+    ///
+    /// ```
+    /// use socketcan_alt::{BitTiming, BitTimingConst};
+    ///
+    /// // SJA1000 with 8 MHz clock
+    /// let btc = BitTimingConst {
+    ///     name: "sja1000".to_owned(),
+    ///     tseg1_min: 1,
+    ///     tseg1_max: 16,
+    ///     tseg2_min: 1,
+    ///     tseg2_max: 8,
+    ///     sjw_max: 4,
+    ///     brp_min: 1,
+    ///     brp_max: 64,
+    ///     brp_inc: 1,
+    /// };
+    /// let calculated = BitTiming::calculate(500_000, None, 8_000_000, &btc)?;
+    /// assert_eq!(calculated.bittiming.sample_point, 875);
+    /// assert_eq!(calculated.bittiming.tq, 125);
+    /// # socketcan_alt::Result::<()>::Ok(())
+    /// ```
+    pub fn calculate(
+        bitrate: u32,
+        sample_point: Option<u32>,
+        clock: u32,
+        btc: &BitTimingConst,
+    ) -> Result<CalculatedBitTiming> {
+        let sample_point_nominal = match sample_point {
+            Some(sample_point) if sample_point != 0 => sample_point,
+            _ if bitrate > 800_000 => 750,
+            _ if bitrate > 500_000 => 800,
+            _ => 875,
+        };
+        if bitrate == 0 || btc.brp_inc == 0 {
+            return Err(Error::InvalidBitTiming {
+                reason: "bitrate and brp_inc must not be zero",
+            });
+        }
+
+        let mut best_bitrate_error = u32::MAX;
+        let mut best_sample_point_error = u32::MAX;
+        let mut best_tseg = 0;
+        let mut best_brp = 0;
+        // tseg even = round down, odd = round up
+        let mut tseg = (btc.tseg1_max + btc.tseg2_max) * 2 + 1;
+        while tseg >= (btc.tseg1_min + btc.tseg2_min) * 2 {
+            let tsegall = SYNC_SEG + tseg / 2;
+            let brp = clock
+                .checked_div(tsegall.wrapping_mul(bitrate))
+                .map(|brp| (brp + tseg % 2) / btc.brp_inc * btc.brp_inc);
+            if let Some(brp) = brp.filter(|brp| (btc.brp_min..=btc.brp_max).contains(brp)) {
+                let bitrate_error = bitrate.abs_diff(clock / (brp * tsegall));
+                if bitrate_error <= best_bitrate_error {
+                    if bitrate_error < best_bitrate_error {
+                        best_sample_point_error = u32::MAX;
+                    }
+                    let (_, _, _, sample_point_error) =
+                        update_sample_point(btc, sample_point_nominal, tseg / 2);
+                    if sample_point_error < best_sample_point_error {
+                        best_sample_point_error = sample_point_error;
+                        best_bitrate_error = bitrate_error;
+                        best_tseg = tseg / 2;
+                        best_brp = brp;
+                        if bitrate_error == 0 && sample_point_error == 0 {
+                            break;
+                        }
+                    }
+                }
+            }
+            if tseg == 0 {
+                break;
+            }
+            tseg -= 1;
+        }
+
+        if best_bitrate_error as u64 * 1000 / bitrate as u64 > MAX_ERROR {
+            return Err(Error::InvalidBitTiming {
+                reason: "bitrate error exceeds 5.0%",
+            });
+        }
+
+        // the real sample point
+        let (sample_point, tseg1, tseg2, _) =
+            update_sample_point(btc, sample_point_nominal, best_tseg);
+        let prop_seg = tseg1 / 2;
+        let phase_seg1 = tseg1 - prop_seg;
+        let phase_seg2 = tseg2;
+        let sjw = 1.max(phase_seg1.min(phase_seg2 / 2));
+        if sjw > btc.sjw_max {
+            return Err(Error::InvalidBitTiming {
+                reason: "sjw exceeds sjw_max",
+            });
+        }
+        if sjw > phase_seg1 || sjw > phase_seg2 {
+            return Err(Error::InvalidBitTiming {
+                reason: "sjw exceeds the phase segments",
+            });
+        }
+        let bittiming = Self {
+            // the real bitrate
+            bitrate: clock / (best_brp * (SYNC_SEG + prop_seg + phase_seg1 + phase_seg2)),
+            sample_point,
+            tq: (best_brp as u64 * 1_000_000_000 / clock as u64) as _,
+            prop_seg,
+            phase_seg1,
+            phase_seg2,
+            sjw,
+            brp: best_brp,
+        };
+        Ok(CalculatedBitTiming {
+            bittiming,
+            bitrate_error: best_bitrate_error,
+            nominal_sample_point: sample_point_nominal,
+            sample_point_error: sample_point_nominal.abs_diff(sample_point),
+        })
+    }
+}
+
+// returns (sample_point, tseg1, tseg2, sample_point_error)
+// as can_update_sample_point, choosing the closest sample point not after the nominal one
+fn update_sample_point(
+    btc: &BitTimingConst,
+    sample_point_nominal: u32,
+    tseg: u32,
+) -> (u32, u32, u32, u32) {
+    let mut best = (0, 0, 0, u32::MAX);
+    for i in 0..=1 {
+        // clamp() of the kernel, which does not panic
+        let mut tseg2 = (tseg + SYNC_SEG)
+            .wrapping_sub(sample_point_nominal * (tseg + SYNC_SEG) / 1000)
+            .wrapping_sub(i)
+            .max(btc.tseg2_min)
+            .min(btc.tseg2_max);
+        let mut tseg1 = tseg.wrapping_sub(tseg2);
+        if tseg1 > btc.tseg1_max {
+            tseg1 = btc.tseg1_max;
+            tseg2 = tseg.wrapping_sub(tseg1);
+        }
+        let sample_point =
+            1000_u32.wrapping_mul((tseg + SYNC_SEG).wrapping_sub(tseg2)) / (tseg + SYNC_SEG);
+        let sample_point_error = sample_point_nominal.abs_diff(sample_point);
+        if sample_point <= sample_point_nominal && sample_point_error < best.3 {
+            best = (sample_point, tseg1, tseg2, sample_point_error);
+        }
+    }
+    best
+}
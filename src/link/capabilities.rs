@@ -0,0 +1,93 @@
+use super::info_data_attrs;
+use crate::netlink::{read, Attrs, Message};
+use crate::{interface, sys, BitTimingConst, CtrlMode, TdcConst};
+
+/// What a CAN controller supports, as reported by its driver.
+/// Fields are `None` or empty if the driver does not report them.
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LinkCapabilities {
+    /// Clock frequency in Hz, used by [`BitTiming::calculate`](crate::BitTiming::calculate).
+    pub clock: Option<u32>,
+    pub bittiming_const: Option<BitTimingConst>,
+    pub data_bittiming_const: Option<BitTimingConst>,
+    /// Control modes the driver accepts (Linux 6.0 or later).
+    pub ctrlmode_supported: Option<CtrlMode>,
+    /// Termination resistances in Ohm. Zero means disabled.
+    pub terminations: Vec<u16>,
+    /// Bitrates of controllers supporting fixed bitrates only.
+    pub bitrates: Vec<u32>,
+    /// Data bitrates of controllers supporting fixed bitrates only.
+    pub data_bitrates: Vec<u32>,
+    /// Maximum bitrate of the transceiver.
+    pub bitrate_max: Option<u32>,
+    pub tdc_const: Option<TdcConst>,
+}
+
+// None for other than CAN interfaces
+pub(crate) fn decode(message: &Message) -> Option<LinkCapabilities> {
+    interface::decode(message)?;
+    let mut capabilities = LinkCapabilities::default();
+    for (ty, payload) in info_data_attrs(message) {
+        match ty {
+            sys::IFLA_CAN_CLOCK => {
+                capabilities.clock = read::<sys::can_clock>(payload).map(|clock| clock.freq)
+            }
+            sys::IFLA_CAN_BITTIMING_CONST => {
+                capabilities.bittiming_const = read(payload).map(BitTimingConst::from_raw)
+            }
+            sys::IFLA_CAN_DATA_BITTIMING_CONST => {
+                capabilities.data_bittiming_const = read(payload).map(BitTimingConst::from_raw)
+            }
+            sys::IFLA_CAN_CTRLMODE_EXT => {
+                for (ty, payload) in Attrs(payload) {
+                    if ty == sys::IFLA_CAN_CTRLMODE_SUPPORTED {
+                        capabilities.ctrlmode_supported =
+                            read(payload).map(CtrlMode::from_bits_truncate);
+                    }
+                }
+            }
+            sys::IFLA_CAN_TERMINATION_CONST => {
+                capabilities.terminations = array(payload, u16::from_ne_bytes)
+            }
+            sys::IFLA_CAN_BITRATE_CONST => {
+                capabilities.bitrates = array(payload, u32::from_ne_bytes)
+            }
+            sys::IFLA_CAN_DATA_BITRATE_CONST => {
+                capabilities.data_bitrates = array(payload, u32::from_ne_bytes)
+            }
+            sys::IFLA_CAN_BITRATE_MAX => capabilities.bitrate_max = read(payload),
+            sys::IFLA_CAN_TDC => {
+                let mut tdc_const = TdcConst::default();
+                let mut found = false;
+                for (ty, payload) in Attrs(payload) {
+                    let field = match ty {
+                        sys::IFLA_CAN_TDC_TDCV_MIN => &mut tdc_const.tdcv_min,
+                        sys::IFLA_CAN_TDC_TDCV_MAX => &mut tdc_const.tdcv_max,
+                        sys::IFLA_CAN_TDC_TDCO_MIN => &mut tdc_const.tdco_min,
+                        sys::IFLA_CAN_TDC_TDCO_MAX => &mut tdc_const.tdco_max,
+                        sys::IFLA_CAN_TDC_TDCF_MIN => &mut tdc_const.tdcf_min,
+                        sys::IFLA_CAN_TDC_TDCF_MAX => &mut tdc_const.tdcf_max,
+                        _ => continue,
+                    };
+                    if let Some(value) = read(payload) {
+                        *field = value;
+                        found = true;
+                    }
+                }
+                if found {
+                    capabilities.tdc_const = Some(tdc_const);
+                }
+            }
+            _ => (),
+        }
+    }
+    Some(capabilities)
+}
+
+fn array<T, const N: usize>(payload: &[u8], f: fn([u8; N]) -> T) -> Vec<T> {
+    payload
+        .chunks_exact(N)
+        .map(|chunk| f(chunk.try_into().unwrap()))
+        .collect()
+}
@@ -0,0 +1,86 @@
+use crate::{sys, BitTiming, CtrlMode, TdcMode};
+
+/// Settings of a CAN controller applied at once by [`CanLink::configure`](crate::CanLink::configure).
+/// Settings not given are left unchanged.
+///
+/// Most drivers reject changes while the interface is up.
+///
+/// ```
+/// use socketcan_alt::{BitTiming, CtrlMode, LinkConfig};
+///
+/// let config = LinkConfig::new()
+///     .bittiming(BitTiming::from_bitrate(500_000, Some(875)))
+///     .data_bittiming(BitTiming::from_bitrate(2_000_000, Some(750)))
+///     .ctrlmode(CtrlMode::FD, true)
+///     .restart_ms(100);
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LinkConfig {
+    pub(super) bittiming: Option<sys::can_bittiming>,
+    pub(super) data_bittiming: Option<sys::can_bittiming>,
+    pub(super) ctrlmode: Option<sys::can_ctrlmode>,
+    pub(super) restart_ms: Option<u32>,
+    pub(super) termination: Option<u16>,
+    pub(super) tdc: Option<TdcMode>,
+}
+
+impl LinkConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bit timing of the arbitration phase.
+    pub fn bittiming(mut self, bittiming: BitTiming) -> Self {
+        self.bittiming = Some(bittiming.into_raw());
+        self
+    }
+
+    /// Bit timing of the data phase of CAN FD frames.
+    pub fn data_bittiming(mut self, bittiming: BitTiming) -> Self {
+        self.data_bittiming = Some(bittiming.into_raw());
+        self
+    }
+
+    /// Enables or disables control modes. Modes not given are left unchanged.
+    pub fn ctrlmode(mut self, mode: CtrlMode, enabled: bool) -> Self {
+        let ctrlmode = self
+            .ctrlmode
+            .get_or_insert(sys::can_ctrlmode { mask: 0, flags: 0 });
+        ctrlmode.mask |= mode.bits();
+        if enabled {
+            ctrlmode.flags |= mode.bits();
+        } else {
+            ctrlmode.flags &= !mode.bits();
+        }
+        self
+    }
+
+    /// Termination resistance in Ohm, one of
+    /// [`LinkCapabilities::terminations`](crate::LinkCapabilities::terminations).
+    /// Zero disables the termination.
+    pub fn termination(mut self, termination: u16) -> Self {
+        self.termination = Some(termination);
+        self
+    }
+
+    /// Transmitter delay compensation of the data phase,
+    /// which sets [`CtrlMode::TDC_AUTO`] or [`CtrlMode::TDC_MANUAL`] accordingly.
+    ///
+    /// Unless the mode is [`TdcMode::Off`], the kernel requires the data bit timing
+    /// and [`CtrlMode::FD`] to be enabled in the same configuration.
+    pub fn tdc(self, mode: TdcMode) -> Self {
+        let mut config = self.ctrlmode(CtrlMode::TDC_AUTO | CtrlMode::TDC_MANUAL, false);
+        config.tdc = Some(mode);
+        match mode {
+            TdcMode::Off => config,
+            TdcMode::Auto { .. } => config.ctrlmode(CtrlMode::TDC_AUTO, true),
+            TdcMode::Manual(_) => config.ctrlmode(CtrlMode::TDC_MANUAL, true),
+        }
+    }
+
+    /// Delay of the automatic restart after bus-off in milliseconds. Zero disables it.
+    pub fn restart_ms(mut self, restart_ms: u32) -> Self {
+        self.restart_ms = Some(restart_ms);
+        self
+    }
+}
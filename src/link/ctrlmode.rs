@@ -0,0 +1,28 @@
+use crate::sys;
+
+bitflags::bitflags! {
+    /// Control modes of a CAN controller (`CAN_CTRLMODE_*`).
+    pub struct CtrlMode: u32 {
+        /// Receives the sent frames in the controller without sending them on the bus.
+        const LOOPBACK = sys::CAN_CTRLMODE_LOOPBACK;
+        /// Does not send frames, error frames or acknowledgements.
+        const LISTENONLY = sys::CAN_CTRLMODE_LISTENONLY;
+        /// Samples each bit three times.
+        const THREE_SAMPLES = sys::CAN_CTRLMODE_3_SAMPLES;
+        /// Does not retransmit frames on errors or lost arbitration.
+        const ONE_SHOT = sys::CAN_CTRLMODE_ONE_SHOT;
+        /// Reports bus errors as error frames.
+        const BERR_REPORTING = sys::CAN_CTRLMODE_BERR_REPORTING;
+        const FD = sys::CAN_CTRLMODE_FD;
+        /// Does not treat missing acknowledgements as errors.
+        const PRESUME_ACK = sys::CAN_CTRLMODE_PRESUME_ACK;
+        /// CAN FD of Bosch specification without the stuff count in the CRC.
+        const FD_NON_ISO = sys::CAN_CTRLMODE_FD_NON_ISO;
+        /// Sends and receives DLC values greater than 8 of classic frames.
+        const CC_LEN8_DLC = sys::CAN_CTRLMODE_CC_LEN8_DLC;
+        /// The controller measures the transmitter delay (see [`TdcMode`](crate::TdcMode)).
+        const TDC_AUTO = sys::CAN_CTRLMODE_TDC_AUTO;
+        /// The transmitter delay is given (see [`TdcMode`](crate::TdcMode)).
+        const TDC_MANUAL = sys::CAN_CTRLMODE_TDC_MANUAL;
+    }
+}
@@ -0,0 +1,74 @@
+use super::status;
+use crate::netlink::Netlink;
+use crate::{sys, Interface, LinkStatus, Result};
+use std::collections::VecDeque;
+use std::os::unix::io::{AsRawFd, RawFd};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LinkEvent {
+    /// An interface was added or its status changed.
+    Changed(LinkStatus),
+    Removed(Interface),
+}
+
+/// A subscription to changes of CAN interfaces (`RTMGRP_LINK`), iterating over [`LinkEvent`]s.
+///
+/// The kernel notifies carrier changes, i.e. bus-off and restarts,
+/// but not the transitions between error active, warning and passive.
+/// Poll [`CanLink::status`](crate::CanLink::status) or receive error frames
+/// (`CAN_ERR_CRTL`) to follow these transitions.
+///
+/// Fails with `ENOBUFS` if notifications were dropped because they were not received in time.
+pub struct LinkMonitor {
+    netlink: Netlink,
+    pending: VecDeque<LinkEvent>,
+}
+
+impl LinkMonitor {
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            netlink: Netlink::new(sys::NETLINK_ROUTE, sys::RTMGRP_LINK)?,
+            pending: VecDeque::new(),
+        })
+    }
+
+    pub fn set_nonblocking(&self, nonblocking: bool) -> Result<()> {
+        self.netlink.set_nonblocking(nonblocking)
+    }
+
+    pub fn recv(&mut self) -> Result<LinkEvent> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Ok(event);
+            }
+            for message in self.netlink.recv()? {
+                let event = match message.ty() {
+                    sys::RTM_NEWLINK => status::decode(&message).map(LinkEvent::Changed),
+                    sys::RTM_DELLINK => crate::interface::decode(&message).map(LinkEvent::Removed),
+                    _ => None,
+                };
+                self.pending.extend(event);
+            }
+        }
+    }
+
+    // events received but not returned yet, which do not make the socket readable
+    #[cfg(feature = "aio")]
+    pub(crate) fn pop_pending(&mut self) -> Option<LinkEvent> {
+        self.pending.pop_front()
+    }
+}
+
+impl Iterator for LinkMonitor {
+    type Item = Result<LinkEvent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.recv())
+    }
+}
+
+impl AsRawFd for LinkMonitor {
+    fn as_raw_fd(&self) -> RawFd {
+        self.netlink.as_raw_fd()
+    }
+}
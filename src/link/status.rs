@@ -0,0 +1,125 @@
+use super::{info_data_attrs, linkinfo_attrs};
+use crate::netlink::{read, Attrs, Message};
+use crate::{interface, sys, Interface, Tdc};
+
+/// The error state of a CAN controller (`enum can_state`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CanState {
+    /// Both error counters are less than 96.
+    ErrorActive,
+    /// An error counter is 96 or more.
+    ErrorWarning,
+    /// An error counter is 128 or more.
+    ErrorPassive,
+    /// The transmit error counter exceeded 255 and the controller left the bus.
+    BusOff,
+    Stopped,
+    Sleeping,
+}
+
+impl CanState {
+    pub(crate) fn from_raw(value: u32) -> Option<Self> {
+        match value {
+            sys::CAN_STATE_ERROR_ACTIVE => Some(Self::ErrorActive),
+            sys::CAN_STATE_ERROR_WARNING => Some(Self::ErrorWarning),
+            sys::CAN_STATE_ERROR_PASSIVE => Some(Self::ErrorPassive),
+            sys::CAN_STATE_BUS_OFF => Some(Self::BusOff),
+            sys::CAN_STATE_STOPPED => Some(Self::Stopped),
+            sys::CAN_STATE_SLEEPING => Some(Self::Sleeping),
+            _ => None,
+        }
+    }
+}
+
+/// Transmit and receive error counters of a CAN controller.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BerrCounter {
+    pub tx: u16,
+    pub rx: u16,
+}
+
+/// Statistics of a CAN controller (`struct can_device_stats`), counted since the driver was loaded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DeviceStats {
+    pub bus_error: u32,
+    /// Number of changes to the error warning state.
+    pub error_warning: u32,
+    /// Number of changes to the error passive state.
+    pub error_passive: u32,
+    /// Number of changes to the bus-off state.
+    pub bus_off: u32,
+    pub arbitration_lost: u32,
+    pub restarts: u32,
+}
+
+/// An interface with the state of its CAN controller.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LinkStatus {
+    pub interface: Interface,
+    /// `None` for interfaces without a controller, e.g. `vcan`.
+    pub state: Option<CanState>,
+    /// `None` if the driver does not report the error counters.
+    pub berr_counter: Option<BerrCounter>,
+    pub stats: Option<DeviceStats>,
+    /// Termination resistance in Ohm, `None` without a switchable termination.
+    pub termination: Option<u16>,
+    /// Transmitter delay compensation, `None` if disabled.
+    pub tdc: Option<Tdc>,
+}
+
+// None for other than CAN interfaces
+pub(crate) fn decode(message: &Message) -> Option<LinkStatus> {
+    let mut status = LinkStatus {
+        interface: interface::decode(message)?,
+        state: None,
+        berr_counter: None,
+        stats: None,
+        termination: None,
+        tdc: None,
+    };
+    for (ty, payload) in info_data_attrs(message) {
+        match ty {
+            sys::IFLA_CAN_STATE => status.state = read(payload).and_then(CanState::from_raw),
+            sys::IFLA_CAN_BERR_COUNTER => {
+                status.berr_counter =
+                    read::<sys::can_berr_counter>(payload).map(|counter| BerrCounter {
+                        tx: counter.txerr,
+                        rx: counter.rxerr,
+                    })
+            }
+            sys::IFLA_CAN_TERMINATION => status.termination = read(payload),
+            sys::IFLA_CAN_TDC => {
+                for (ty, payload) in Attrs(payload) {
+                    let field = match ty {
+                        sys::IFLA_CAN_TDC_TDCV => {
+                            &mut status.tdc.get_or_insert_with(Tdc::default).tdcv
+                        }
+                        sys::IFLA_CAN_TDC_TDCO => {
+                            &mut status.tdc.get_or_insert_with(Tdc::default).tdco
+                        }
+                        sys::IFLA_CAN_TDC_TDCF => {
+                            &mut status.tdc.get_or_insert_with(Tdc::default).tdcf
+                        }
+                        _ => continue,
+                    };
+                    *field = read(payload).unwrap_or_default();
+                }
+            }
+            _ => (),
+        }
+    }
+    for (ty, payload) in linkinfo_attrs(message) {
+        if ty == sys::IFLA_INFO_XSTATS {
+            status.stats = read::<sys::can_device_stats>(payload).map(|stats| DeviceStats {
+                bus_error: stats.bus_error,
+                error_warning: stats.error_warning,
+                error_passive: stats.error_passive,
+                bus_off: stats.bus_off,
+                arbitration_lost: stats.arbitration_lost,
+                restarts: stats.restarts,
+            });
+        }
+    }
+    Some(status)
+}
@@ -0,0 +1,82 @@
+use crate::BitTiming;
+
+/// Transmitter delay compensation of the data phase of CAN FD (ISO 11898-1 section 11.3.3),
+/// in clock periods.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Tdc {
+    /// Transmitter delay compensation value, measured by the controller if zero.
+    pub tdcv: u32,
+    /// Transmitter delay compensation offset.
+    pub tdco: u32,
+    /// Transmitter delay compensation filter window.
+    pub tdcf: u32,
+}
+
+/// Limits of [`Tdc`] (`struct can_tdc_const`). Zero maximums mean unsupported parameters.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct TdcConst {
+    pub tdcv_min: u32,
+    pub tdcv_max: u32,
+    pub tdco_min: u32,
+    pub tdco_max: u32,
+    pub tdcf_min: u32,
+    pub tdcf_max: u32,
+}
+
+/// Transmitter delay compensation set by [`LinkConfig::tdc`](crate::LinkConfig::tdc).
+///
+/// If not set, the kernel enables [`TdcMode::Auto`] with the offset of [`Tdc::calculate`]
+/// when the data bit timing is configured.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TdcMode {
+    Off,
+    /// The controller measures `tdcv`.
+    Auto {
+        tdco: u32,
+        tdcf: u32,
+    },
+    Manual(Tdc),
+}
+
+impl Tdc {
+    /// Calculates the offset for the data bit timing as `can_calc_tdco` of the kernel,
+    /// i.e. the sample point in clock periods, with `tdcv` measured by the controller.
+    ///
+    /// Returns `None` if TDC is not applicable, i.e. the prescaler is more than two
+    /// or the sample point is below `tdco_min`.
+    pub fn calculate(data_bittiming: &BitTiming, tdc_const: &TdcConst) -> Option<Self> {
+        let dbt = data_bittiming;
+        if dbt.brp != 1 && dbt.brp != 2 {
+            return None;
+        }
+        let sample_point = (super::calc::SYNC_SEG + dbt.prop_seg + dbt.phase_seg1) * dbt.brp;
+        if sample_point < tdc_const.tdco_min {
+            return None;
+        }
+        Some(Self {
+            tdcv: 0,
+            tdco: sample_point.min(tdc_const.tdco_max),
+            tdcf: 0,
+        })
+    }
+}
+
+impl TdcConst {
+    /// Checks that the parameters are within the limits.
+    /// The filter window is checked only if the controller supports it (`tdcf_max != 0`).
+    pub fn contains(&self, mode: &TdcMode) -> bool {
+        let (tdcv, tdco, tdcf) = match *mode {
+            TdcMode::Off => return true,
+            TdcMode::Auto { tdco, tdcf } => (None, tdco, tdcf),
+            TdcMode::Manual(tdc) => (Some(tdc.tdcv), tdc.tdco, tdc.tdcf),
+        };
+        tdcv.into_iter()
+            .all(|tdcv| (self.tdcv_min..=self.tdcv_max).contains(&tdcv))
+            && (self.tdco_min..=self.tdco_max).contains(&tdco)
+            && if self.tdcf_max == 0 {
+                tdcf == 0
+            } else {
+                (self.tdcf_min..=self.tdcf_max).contains(&tdcf)
+            }
+    }
+}
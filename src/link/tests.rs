@@ -0,0 +1,629 @@
+use super::{
+    capabilities, encode, set_mtu, status, validate, BitTiming, CanLink, CtrlMode, LinkConfig,
+    TdcMode,
+};
+use crate::netlink::{bytes_of, read, read_string, Attrs, Message, MessageBuilder};
+use crate::socket::tests::{ifname, LOCK};
+use crate::{
+    sys, BerrCounter, BitTimingConst, CanState, DeviceStats, Error, InterfaceFlags,
+    LinkCapabilities, LinkEvent, LinkKind, LinkMonitor, MtuClass, Tdc, TdcConst,
+};
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::io::ErrorKind;
+use std::mem::MaybeUninit;
+
+macro_rules! lock {
+    (shared) => {
+        let _lock = LOCK.read();
+    };
+    (exclusive) => {
+        let _lock = LOCK.write();
+    };
+}
+
+// attributes in IFLA_INFO_DATA
+fn info_data(config: &LinkConfig) -> HashMap<u32, Vec<u8>> {
+    let message = Message::parse(&encode(42, config).finish()).pop().unwrap();
+    assert_eq!(message.ty(), sys::RTM_NEWLINK);
+    let header = message.family_header::<sys::ifinfomsg>().unwrap();
+    assert_eq!(header.ifi_index, 42);
+    assert_eq!(header.ifi_change, 0);
+
+    let (ty, linkinfo) = message.attrs::<sys::ifinfomsg>().next().unwrap();
+    assert_eq!(ty, sys::IFLA_LINKINFO);
+    let mut linkinfo = Attrs(linkinfo);
+    let (ty, kind) = linkinfo.next().unwrap();
+    assert_eq!(ty, sys::IFLA_INFO_KIND);
+    assert_eq!(read_string(kind), "can");
+    let (ty, data) = linkinfo.next().unwrap();
+    assert_eq!(ty, sys::IFLA_INFO_DATA);
+    Attrs(data)
+        .map(|(ty, payload)| (ty, payload.to_vec()))
+        .collect()
+}
+
+#[test]
+fn test_encode_empty() {
+    assert!(info_data(&LinkConfig::new()).is_empty());
+}
+
+#[test]
+fn test_encode() {
+    let config = LinkConfig::new()
+        .bittiming(BitTiming::from_bitrate(500_000, Some(875)))
+        .data_bittiming(BitTiming::from_tq(25, 10, 10, 9, 4))
+        .ctrlmode(CtrlMode::FD | CtrlMode::ONE_SHOT, true)
+        .restart_ms(100);
+    let attrs = info_data(&config);
+    assert_eq!(attrs.len(), 4);
+
+    let bittiming = read::<sys::can_bittiming>(&attrs[&sys::IFLA_CAN_BITTIMING]).unwrap();
+    assert_eq!(
+        (bittiming.bitrate, bittiming.sample_point, bittiming.tq),
+        (500_000, 875, 0)
+    );
+    let bittiming = read::<sys::can_bittiming>(&attrs[&sys::IFLA_CAN_DATA_BITTIMING]).unwrap();
+    assert_eq!(
+        (
+            bittiming.bitrate,
+            bittiming.tq,
+            bittiming.prop_seg,
+            bittiming.phase_seg1,
+            bittiming.phase_seg2,
+            bittiming.sjw
+        ),
+        (0, 25, 10, 10, 9, 4)
+    );
+    let ctrlmode = read::<sys::can_ctrlmode>(&attrs[&sys::IFLA_CAN_CTRLMODE]).unwrap();
+    assert_eq!(
+        ctrlmode.mask,
+        sys::CAN_CTRLMODE_FD | sys::CAN_CTRLMODE_ONE_SHOT
+    );
+    assert_eq!(ctrlmode.flags, ctrlmode.mask);
+    assert_eq!(read::<u32>(&attrs[&sys::IFLA_CAN_RESTART_MS]), Some(100));
+}
+
+#[test]
+fn test_encode_tdc() {
+    let config = LinkConfig::new()
+        .termination(120)
+        .tdc(TdcMode::Auto { tdco: 15, tdcf: 0 });
+    let attrs = info_data(&config);
+    assert_eq!(read::<u16>(&attrs[&sys::IFLA_CAN_TERMINATION]), Some(120));
+    let tdc = Attrs(&attrs[&sys::IFLA_CAN_TDC])
+        .map(|(ty, payload)| (ty, read::<u32>(payload).unwrap()))
+        .collect::<Vec<_>>();
+    assert_eq!(tdc, [(sys::IFLA_CAN_TDC_TDCO, 15)]);
+
+    let config = LinkConfig::new().tdc(TdcMode::Manual(Tdc {
+        tdcv: 4,
+        tdco: 15,
+        tdcf: 20,
+    }));
+    let attrs = info_data(&config);
+    let tdc = Attrs(&attrs[&sys::IFLA_CAN_TDC])
+        .map(|(ty, payload)| (ty, read::<u32>(payload).unwrap()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        tdc,
+        [
+            (sys::IFLA_CAN_TDC_TDCV, 4),
+            (sys::IFLA_CAN_TDC_TDCO, 15),
+            (sys::IFLA_CAN_TDC_TDCF, 20)
+        ]
+    );
+
+    let attrs = info_data(&LinkConfig::new().tdc(TdcMode::Off));
+    assert!(!attrs.contains_key(&sys::IFLA_CAN_TDC));
+}
+
+#[test]
+fn test_tdc_ctrlmode() {
+    let config = LinkConfig::new()
+        .tdc(TdcMode::Auto { tdco: 15, tdcf: 0 })
+        .tdc(TdcMode::Manual(Tdc::default()));
+    let ctrlmode = config.ctrlmode.unwrap();
+    assert_eq!(
+        CtrlMode::from_bits(ctrlmode.mask),
+        Some(CtrlMode::TDC_AUTO | CtrlMode::TDC_MANUAL)
+    );
+    assert_eq!(
+        CtrlMode::from_bits(ctrlmode.flags),
+        Some(CtrlMode::TDC_MANUAL)
+    );
+
+    let ctrlmode = LinkConfig::new().tdc(TdcMode::Off).ctrlmode.unwrap();
+    assert_eq!(ctrlmode.flags, 0);
+}
+
+#[test]
+fn test_tdc_const_contains() {
+    let tdc_const = TdcConst {
+        tdcv_min: 1,
+        tdcv_max: 63,
+        tdco_max: 63,
+        ..TdcConst::default()
+    };
+    assert!(tdc_const.contains(&TdcMode::Off));
+    assert!(tdc_const.contains(&TdcMode::Auto { tdco: 63, tdcf: 0 }));
+    assert!(!tdc_const.contains(&TdcMode::Auto { tdco: 64, tdcf: 0 }));
+    // no filter window
+    assert!(!tdc_const.contains(&TdcMode::Auto { tdco: 15, tdcf: 1 }));
+    // TDCV is checked in manual mode only
+    assert!(tdc_const.contains(&TdcMode::Manual(Tdc {
+        tdcv: 1,
+        tdco: 15,
+        tdcf: 0
+    })));
+    assert!(!tdc_const.contains(&TdcMode::Manual(Tdc {
+        tdcv: 0,
+        tdco: 15,
+        tdcf: 0
+    })));
+}
+
+#[test]
+fn test_validate() {
+    let capabilities = LinkCapabilities {
+        terminations: vec![0, 120],
+        ..LinkCapabilities::default()
+    };
+    assert!(validate(&LinkConfig::new().termination(120), &capabilities).is_ok());
+    assert!(matches!(
+        validate(&LinkConfig::new().termination(60), &capabilities),
+        Err(Error::InvalidLinkConfig { .. })
+    ));
+    assert!(validate(&LinkConfig::new().tdc(TdcMode::Off), &capabilities).is_ok());
+    let fd = LinkConfig::new()
+        .data_bittiming(BitTiming::from_bitrate(2_000_000, Some(750)))
+        .ctrlmode(CtrlMode::FD, true);
+    let config = fd.tdc(TdcMode::Auto { tdco: 15, tdcf: 0 });
+    assert!(matches!(
+        validate(&config, &capabilities),
+        Err(Error::InvalidLinkConfig { .. })
+    ));
+
+    let (_, _, tdc_const) = mcp251xfd();
+    let capabilities = LinkCapabilities {
+        tdc_const: Some(tdc_const),
+        ..LinkCapabilities::default()
+    };
+    assert!(validate(&config, &capabilities).is_ok());
+    let config = fd.tdc(TdcMode::Auto {
+        tdco: tdc_const.tdco_max + 1,
+        tdcf: 0,
+    });
+    assert!(matches!(
+        validate(&config, &capabilities),
+        Err(Error::InvalidLinkConfig { .. })
+    ));
+
+    // rejected by the kernel without the data bit timing or CAN FD
+    let tdc = TdcMode::Auto { tdco: 15, tdcf: 0 };
+    for config in [
+        LinkConfig::new().tdc(tdc),
+        LinkConfig::new().ctrlmode(CtrlMode::FD, true).tdc(tdc),
+        fd.ctrlmode(CtrlMode::FD, false).tdc(tdc),
+    ] {
+        assert!(matches!(
+            validate(&config, &capabilities),
+            Err(Error::InvalidLinkConfig { .. })
+        ));
+    }
+}
+
+#[test]
+fn test_set_mtu() {
+    let message = Message::parse(&set_mtu(42, MtuClass::Fd).finish())
+        .pop()
+        .unwrap();
+    assert_eq!(message.ty(), sys::RTM_NEWLINK);
+    let header = message.family_header::<sys::ifinfomsg>().unwrap();
+    assert_eq!((header.ifi_index, header.ifi_change), (42, 0));
+    let attrs = message.attrs::<sys::ifinfomsg>().collect::<Vec<_>>();
+    assert_eq!(attrs.len(), 1);
+    assert_eq!(attrs[0].0, sys::IFLA_MTU);
+    assert_eq!(read::<u32>(attrs[0].1), Some(sys::CANFD_MTU as u32));
+}
+
+#[test]
+fn test_ctrlmode() {
+    let config = LinkConfig::new()
+        .ctrlmode(CtrlMode::LOOPBACK | CtrlMode::LISTENONLY, true)
+        .ctrlmode(CtrlMode::LISTENONLY | CtrlMode::BERR_REPORTING, false);
+    let ctrlmode = config.ctrlmode.unwrap();
+    assert_eq!(
+        CtrlMode::from_bits(ctrlmode.mask),
+        Some(CtrlMode::LOOPBACK | CtrlMode::LISTENONLY | CtrlMode::BERR_REPORTING)
+    );
+    assert_eq!(
+        CtrlMode::from_bits(ctrlmode.flags),
+        Some(CtrlMode::LOOPBACK)
+    );
+}
+
+#[test]
+fn test_new_no_device() {
+    let ifname = CString::new("NO DEVICE").unwrap();
+    assert!(matches!(
+        CanLink::new(ifname),
+        Err(Error::InterfaceNotFound { .. })
+    ));
+}
+
+#[test]
+#[ignore]
+fn test_set_up() {
+    lock!(exclusive);
+    let link = CanLink::new(ifname()).unwrap();
+    let up = || {
+        crate::list_interfaces()
+            .unwrap()
+            .into_iter()
+            .find(|interface| interface.index == link.ifindex())
+            .unwrap()
+            .flags
+            .contains(InterfaceFlags::UP)
+    };
+    link.set_up(false).unwrap();
+    assert!(!up());
+    link.set_up(true).unwrap();
+    assert!(up());
+}
+
+#[test]
+#[ignore]
+fn test_configure_up() {
+    lock!(exclusive);
+    let link = CanLink::new(ifname()).unwrap();
+    link.set_up(true).unwrap();
+    // vcan does not support bit timing and physical controllers reject changes while up
+    let config = LinkConfig::new().bittiming(BitTiming::from_bitrate(500_000, None));
+    assert!(matches!(
+        link.configure(&config),
+        Err(Error::Netlink { .. })
+    ));
+}
+
+#[test]
+fn test_decode_status() {
+    let mut header = unsafe { MaybeUninit::<sys::ifinfomsg>::zeroed().assume_init() };
+    header.ifi_type = libc::ARPHRD_CAN;
+    header.ifi_index = 42;
+    let stats = sys::can_device_stats {
+        bus_error: 1,
+        error_warning: 2,
+        error_passive: 3,
+        bus_off: 4,
+        arbitration_lost: 5,
+        restarts: 6,
+    };
+    let mut message = MessageBuilder::new(sys::RTM_NEWLINK, 0, &header);
+    message.nest(sys::IFLA_LINKINFO, |message| {
+        message
+            .attr_string(sys::IFLA_INFO_KIND, "can")
+            .nest(sys::IFLA_INFO_DATA, |message| {
+                message
+                    .attr_value(sys::IFLA_CAN_STATE, &sys::CAN_STATE_ERROR_PASSIVE)
+                    .attr_value(
+                        sys::IFLA_CAN_BERR_COUNTER,
+                        &sys::can_berr_counter {
+                            txerr: 128,
+                            rxerr: 7,
+                        },
+                    )
+                    .attr_value(sys::IFLA_CAN_TERMINATION, &120_u16)
+                    .nest(sys::IFLA_CAN_TDC, |message| {
+                        message
+                            .attr_value(sys::IFLA_CAN_TDC_TDCV_MAX, &63_u32)
+                            .attr_value(sys::IFLA_CAN_TDC_TDCO, &15_u32);
+                    });
+            })
+            .attr(sys::IFLA_INFO_XSTATS, bytes_of(&stats));
+    });
+    let message = Message::parse(&message.finish()).pop().unwrap();
+
+    let status = status::decode(&message).unwrap();
+    assert_eq!(status.interface.index, 42);
+    assert_eq!(status.interface.kind, LinkKind::Physical { driver: None });
+    assert_eq!(status.state, Some(CanState::ErrorPassive));
+    assert_eq!(status.berr_counter, Some(BerrCounter { tx: 128, rx: 7 }));
+    assert_eq!(status.termination, Some(120));
+    assert_eq!(
+        status.tdc,
+        Some(Tdc {
+            tdcv: 0,
+            tdco: 15,
+            tdcf: 0
+        })
+    );
+    assert_eq!(
+        status.stats,
+        Some(DeviceStats {
+            bus_error: 1,
+            error_warning: 2,
+            error_passive: 3,
+            bus_off: 4,
+            arbitration_lost: 5,
+            restarts: 6,
+        })
+    );
+}
+
+#[test]
+fn test_can_state_order() {
+    assert!(CanState::ErrorActive < CanState::ErrorWarning);
+    assert!(CanState::ErrorPassive < CanState::BusOff);
+    assert_eq!(CanState::from_raw(sys::CAN_STATE_MAX), None);
+}
+
+#[test]
+fn test_monitor_nonblocking() {
+    let mut monitor = LinkMonitor::new().unwrap();
+    monitor.set_nonblocking(true).unwrap();
+    assert_eq!(monitor.recv().unwrap_err().kind(), ErrorKind::WouldBlock);
+}
+
+#[test]
+#[ignore]
+fn test_status() {
+    lock!(shared);
+    let link = CanLink::new(ifname()).unwrap();
+    let status = link.status().unwrap();
+    assert_eq!(status.interface.index, link.ifindex());
+    if status.interface.kind == LinkKind::Vcan {
+        assert_eq!(status.state, None);
+        assert_eq!(link.state().unwrap_err().kind(), ErrorKind::Unsupported);
+    } else {
+        assert!(status.state.is_some());
+    }
+}
+
+#[test]
+#[ignore]
+fn test_monitor() {
+    lock!(exclusive);
+    let link = CanLink::new(ifname()).unwrap();
+    let mut monitor = LinkMonitor::new().unwrap();
+    link.set_up(false).unwrap();
+    link.set_up(true).unwrap();
+    match monitor.next().unwrap().unwrap() {
+        LinkEvent::Changed(status) => assert_eq!(status.interface.index, link.ifindex()),
+        event => panic!("{:?}", event),
+    }
+}
+
+fn sja1000() -> BitTimingConst {
+    BitTimingConst {
+        name: "sja1000".to_owned(),
+        tseg1_min: 1,
+        tseg1_max: 16,
+        tseg2_min: 1,
+        tseg2_max: 8,
+        sjw_max: 4,
+        brp_min: 1,
+        brp_max: 64,
+        brp_inc: 1,
+    }
+}
+
+fn mcp251xfd() -> (BitTimingConst, BitTimingConst, TdcConst) {
+    let nominal = BitTimingConst {
+        name: "mcp251xfd".to_owned(),
+        tseg1_min: 2,
+        tseg1_max: 256,
+        tseg2_min: 1,
+        tseg2_max: 128,
+        sjw_max: 128,
+        brp_min: 1,
+        brp_max: 256,
+        brp_inc: 1,
+    };
+    let data = BitTimingConst {
+        name: "mcp251xfd".to_owned(),
+        tseg1_min: 1,
+        tseg1_max: 32,
+        tseg2_min: 1,
+        tseg2_max: 16,
+        sjw_max: 16,
+        brp_min: 1,
+        brp_max: 256,
+        brp_inc: 1,
+    };
+    let tdc = TdcConst {
+        tdcv_min: 0,
+        tdcv_max: 63,
+        tdco_min: 0,
+        tdco_max: 63,
+        tdcf_min: 0,
+        tdcf_max: 0,
+    };
+    (nominal, data, tdc)
+}
+
+#[test]
+fn test_calculate_sja1000() {
+    // `ip -details link show` of SJA1000 with 8 MHz clock
+    let calculated = BitTiming::calculate(500_000, None, 8_000_000, &sja1000()).unwrap();
+    assert_eq!(
+        calculated.bittiming,
+        BitTiming {
+            bitrate: 500_000,
+            sample_point: 875,
+            tq: 125,
+            prop_seg: 6,
+            phase_seg1: 7,
+            phase_seg2: 2,
+            sjw: 1,
+            brp: 1,
+        }
+    );
+    assert_eq!(calculated.bitrate_error, 0);
+    assert_eq!(calculated.sample_point_error, 0);
+
+    let calculated = BitTiming::calculate(125_000, None, 8_000_000, &sja1000()).unwrap();
+    assert_eq!(
+        calculated.bittiming,
+        BitTiming {
+            bitrate: 125_000,
+            sample_point: 875,
+            tq: 500,
+            prop_seg: 6,
+            phase_seg1: 7,
+            phase_seg2: 2,
+            sjw: 1,
+            brp: 4,
+        }
+    );
+}
+
+#[test]
+fn test_calculate_mcp251xfd() {
+    // `ip -details link show` of MCP2518FD with 40 MHz clock
+    let (nominal, data, tdc) = mcp251xfd();
+    let calculated = BitTiming::calculate(500_000, None, 40_000_000, &nominal).unwrap();
+    assert_eq!(
+        calculated.bittiming,
+        BitTiming {
+            bitrate: 500_000,
+            sample_point: 875,
+            tq: 25,
+            prop_seg: 34,
+            phase_seg1: 35,
+            phase_seg2: 10,
+            sjw: 5,
+            brp: 1,
+        }
+    );
+
+    let calculated = BitTiming::calculate(2_000_000, None, 40_000_000, &data).unwrap();
+    assert_eq!(calculated.nominal_sample_point, 750);
+    assert_eq!(
+        calculated.bittiming,
+        BitTiming {
+            bitrate: 2_000_000,
+            sample_point: 750,
+            tq: 25,
+            prop_seg: 7,
+            phase_seg1: 7,
+            phase_seg2: 5,
+            sjw: 2,
+            brp: 1,
+        }
+    );
+    assert_eq!(
+        Tdc::calculate(&calculated.bittiming, &tdc),
+        Some(Tdc {
+            tdcv: 0,
+            tdco: 15,
+            tdcf: 0,
+        })
+    );
+}
+
+#[test]
+fn test_calculate_error() {
+    // 8 MHz / 83333 bit/s rounds to 96 clock periods per bit (16 time quanta of 6),
+    // which gives 83333 bit/s back without error and reaches 87.5%
+    let calculated = BitTiming::calculate(83_333, None, 8_000_000, &sja1000()).unwrap();
+    assert_eq!(calculated.bittiming.brp, 6);
+    assert_eq!(calculated.bittiming.bitrate, 83_333);
+    assert_eq!(calculated.bitrate_error, 0);
+    assert_eq!(calculated.bittiming.sample_point, 875);
+
+    let calculated = BitTiming::calculate(500_000, Some(800), 8_000_000, &sja1000()).unwrap();
+    assert_eq!(calculated.bittiming.sample_point, 750);
+    assert_eq!(calculated.sample_point_error, 50);
+
+    // 8 MHz cannot be divided into 3 Mbit/s with at least 4 time quanta
+    assert!(matches!(
+        BitTiming::calculate(3_000_000, None, 8_000_000, &sja1000()),
+        Err(Error::InvalidBitTiming { .. })
+    ));
+}
+
+#[test]
+fn test_tdc_not_applicable() {
+    let (_, data, tdc) = mcp251xfd();
+    let calculated = BitTiming::calculate(500_000, None, 40_000_000, &data).unwrap();
+    assert!(calculated.bittiming.brp > 2);
+    assert_eq!(Tdc::calculate(&calculated.bittiming, &tdc), None);
+}
+
+#[test]
+fn test_decode_capabilities() {
+    let mut header = unsafe { MaybeUninit::<sys::ifinfomsg>::zeroed().assume_init() };
+    header.ifi_type = libc::ARPHRD_CAN;
+    let mut btc = unsafe { MaybeUninit::<sys::can_bittiming_const>::zeroed().assume_init() };
+    for (c, &b) in btc.name.iter_mut().zip(b"mcp251xfd") {
+        *c = b as _;
+    }
+    btc.tseg1_min = 2;
+    btc.tseg1_max = 256;
+    btc.brp_inc = 1;
+    let mut message = MessageBuilder::new(sys::RTM_NEWLINK, 0, &header);
+    message.nest(sys::IFLA_LINKINFO, |message| {
+        message
+            .attr_string(sys::IFLA_INFO_KIND, "can")
+            .nest(sys::IFLA_INFO_DATA, |message| {
+                message
+                    .attr_value(sys::IFLA_CAN_CLOCK, &sys::can_clock { freq: 40_000_000 })
+                    .attr_value(sys::IFLA_CAN_BITTIMING_CONST, &btc)
+                    .attr(sys::IFLA_CAN_TERMINATION_CONST, bytes_of(&[0_u16, 120]))
+                    .attr(
+                        sys::IFLA_CAN_BITRATE_CONST,
+                        bytes_of(&[125_000_u32, 250_000, 500_000]),
+                    )
+                    .attr_value(sys::IFLA_CAN_BITRATE_MAX, &8_000_000_u32)
+                    .nest(sys::IFLA_CAN_CTRLMODE_EXT, |message| {
+                        message.attr_value(
+                            sys::IFLA_CAN_CTRLMODE_SUPPORTED,
+                            &(sys::CAN_CTRLMODE_FD | sys::CAN_CTRLMODE_LOOPBACK),
+                        );
+                    })
+                    .nest(sys::IFLA_CAN_TDC, |message| {
+                        message
+                            .attr_value(sys::IFLA_CAN_TDC_TDCV_MAX, &63_u32)
+                            .attr_value(sys::IFLA_CAN_TDC_TDCO_MAX, &63_u32);
+                    });
+            });
+    });
+    let message = Message::parse(&message.finish()).pop().unwrap();
+
+    let capabilities = capabilities::decode(&message).unwrap();
+    let expected = LinkCapabilities {
+        clock: Some(40_000_000),
+        bittiming_const: Some(BitTimingConst {
+            name: "mcp251xfd".to_owned(),
+            tseg1_min: 2,
+            tseg1_max: 256,
+            brp_inc: 1,
+            ..BitTimingConst::default()
+        }),
+        ctrlmode_supported: Some(CtrlMode::FD | CtrlMode::LOOPBACK),
+        terminations: vec![0, 120],
+        bitrates: vec![125_000, 250_000, 500_000],
+        bitrate_max: Some(8_000_000),
+        tdc_const: Some(TdcConst {
+            tdcv_max: 63,
+            tdco_max: 63,
+            ..TdcConst::default()
+        }),
+        ..LinkCapabilities::default()
+    };
+    assert_eq!(capabilities, expected);
+}
+
+#[test]
+#[ignore]
+fn test_capabilities() {
+    lock!(shared);
+    let link = CanLink::new(ifname()).unwrap();
+    let capabilities = link.capabilities().unwrap();
+    if link.status().unwrap().interface.kind == LinkKind::Vcan {
+        assert_eq!(capabilities, LinkCapabilities::default());
+    } else {
+        assert!(capabilities.clock.is_some());
+    }
+}
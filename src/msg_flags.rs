@@ -0,0 +1,8 @@
+bitflags::bitflags! {
+    pub struct MsgFlags: i32 {
+        /// The frame was sent from this host.
+        const DONTROUTE = libc::MSG_DONTROUTE;
+        /// The frame was sent from the receiving socket itself (see `set_recv_own_msgs`).
+        const CONFIRM = libc::MSG_CONFIRM;
+    }
+}
@@ -0,0 +1,328 @@
+use crate::{sys, Error, Result};
+use std::mem::{size_of, size_of_val, MaybeUninit};
+use std::os::raw::c_int;
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::sync::{Mutex, PoisonError};
+use std::{ptr, slice};
+
+const ALIGNTO: usize = 4;
+const RECV_BUF_SIZE: usize = 65536;
+
+fn align(len: usize) -> usize {
+    (len + ALIGNTO - 1) & !(ALIGNTO - 1)
+}
+
+pub(crate) fn bytes_of<T>(value: &T) -> &[u8] {
+    unsafe { slice::from_raw_parts(value as *const _ as *const u8, size_of_val(value)) }
+}
+
+// reads a structure from the head of `bytes`
+pub(crate) fn read<T: Copy>(bytes: &[u8]) -> Option<T> {
+    if bytes.len() < size_of::<T>() {
+        return None;
+    }
+    Some(unsafe { ptr::read_unaligned(bytes.as_ptr() as *const T) })
+}
+
+// reads a NUL-terminated string attribute
+pub(crate) fn read_string(bytes: &[u8]) -> String {
+    let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..len]).into_owned()
+}
+
+/// A netlink socket sending requests to the kernel, or receiving notifications of multicast groups.
+pub(crate) struct Netlink {
+    fd: RawFd,
+    // the next sequence number, locked during a request
+    // so that concurrent requests do not receive the replies to each other
+    seq: Mutex<u32>,
+}
+
+impl Netlink {
+    pub(crate) fn new(protocol: u32, groups: u32) -> Result<Self> {
+        let fd = unsafe {
+            libc::socket(
+                libc::AF_NETLINK,
+                libc::SOCK_RAW | libc::SOCK_CLOEXEC,
+                protocol as _,
+            )
+        };
+        if fd == -1 {
+            return Err(Error::last_os_error());
+        }
+        let netlink = Self {
+            fd,
+            seq: Mutex::new(1),
+        };
+
+        let mut address = unsafe { MaybeUninit::<sys::sockaddr_nl>::zeroed().assume_init() };
+        address.nl_family = libc::AF_NETLINK as _;
+        address.nl_groups = groups;
+        if unsafe {
+            libc::bind(
+                netlink.as_raw_fd(),
+                &address as *const _ as _,
+                size_of_val(&address) as _,
+            )
+        } != 0
+        {
+            return Err(Error::last_os_error());
+        }
+        // best effort: error messages are available since Linux 4.12
+        unsafe {
+            libc::setsockopt(
+                netlink.as_raw_fd(),
+                libc::SOL_NETLINK,
+                sys::NETLINK_EXT_ACK as _,
+                &(1 as c_int) as *const _ as _,
+                size_of::<c_int>() as _,
+            )
+        };
+        Ok(netlink)
+    }
+
+    pub(crate) fn route() -> Result<Self> {
+        Self::new(sys::NETLINK_ROUTE, 0)
+    }
+
+    /// Sends a request and collects the replies.
+    /// A dump request ends with `NLMSG_DONE` and the others end with an acknowledgement.
+    pub(crate) fn request(&self, mut message: MessageBuilder) -> Result<Vec<Message>> {
+        let mut next_seq = self.seq.lock().unwrap_or_else(PoisonError::into_inner);
+        let seq = *next_seq;
+        *next_seq = seq.wrapping_add(1);
+        let dump = message.header().nlmsg_flags & sys::NLM_F_DUMP as u16 != 0;
+        message.header_mut().nlmsg_seq = seq;
+        if !dump {
+            message.header_mut().nlmsg_flags |= sys::NLM_F_ACK as u16;
+        }
+        let buf = message.finish();
+        if unsafe { libc::send(self.as_raw_fd(), buf.as_ptr() as _, buf.len(), 0) } as usize
+            != buf.len()
+        {
+            return Err(Error::last_os_error());
+        }
+
+        let mut replies = Vec::new();
+        loop {
+            for message in self.recv()? {
+                if message.header.nlmsg_seq != seq {
+                    continue;
+                }
+                match message.header.nlmsg_type as u32 {
+                    sys::NLMSG_ERROR => {
+                        return match message.error() {
+                            None => Ok(replies),
+                            Some(e) => Err(e),
+                        }
+                    }
+                    sys::NLMSG_DONE => {
+                        return match read::<c_int>(&message.payload) {
+                            Some(errno) if errno < 0 => Err(Error::Netlink {
+                                errno: -errno,
+                                message: None,
+                            }),
+                            _ => Ok(replies),
+                        }
+                    }
+                    sys::NLMSG_NOOP => (),
+                    _ => replies.push(message),
+                }
+            }
+        }
+    }
+
+    /// Receives the messages of a datagram.
+    pub(crate) fn recv(&self) -> Result<Vec<Message>> {
+        let mut buf = vec![0; RECV_BUF_SIZE];
+        let size = unsafe { libc::recv(self.as_raw_fd(), buf.as_mut_ptr() as _, buf.len(), 0) };
+        if size < 0 {
+            return Err(Error::last_os_error());
+        }
+        buf.truncate(size as _);
+        Ok(Message::parse(&buf))
+    }
+
+    pub(crate) fn set_nonblocking(&self, nonblocking: bool) -> Result<()> {
+        if unsafe { libc::ioctl(self.as_raw_fd(), libc::FIONBIO, &(nonblocking as c_int)) } != 0 {
+            return Err(Error::last_os_error());
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Netlink {
+    fn drop(&mut self) {
+        unsafe { libc::close(self.as_raw_fd()) };
+    }
+}
+
+impl AsRawFd for Netlink {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd
+    }
+}
+
+/// A netlink message under construction, with `nlmsg_len` filled in by `finish`.
+pub(crate) struct MessageBuilder(Vec<u8>);
+
+impl MessageBuilder {
+    pub(crate) fn new<T>(ty: u32, flags: u32, header: &T) -> Self {
+        let mut nlmsghdr = unsafe { MaybeUninit::<sys::nlmsghdr>::zeroed().assume_init() };
+        nlmsghdr.nlmsg_type = ty as _;
+        nlmsghdr.nlmsg_flags = (sys::NLM_F_REQUEST | flags) as _;
+        let mut builder = Self(Vec::new());
+        builder.push(bytes_of(&nlmsghdr));
+        builder.push(bytes_of(header));
+        builder
+    }
+
+    /// Appends bytes with padding, e.g. the family header of a nested message.
+    pub(crate) fn push(&mut self, bytes: &[u8]) -> &mut Self {
+        self.0.extend_from_slice(bytes);
+        self.0.resize(align(self.0.len()), 0);
+        self
+    }
+
+    fn header(&self) -> sys::nlmsghdr {
+        read(&self.0).unwrap()
+    }
+
+    fn header_mut(&mut self) -> &mut sys::nlmsghdr {
+        assert!(self.0.len() >= size_of::<sys::nlmsghdr>());
+        unsafe { &mut *(self.0.as_mut_ptr() as *mut sys::nlmsghdr) }
+    }
+
+    pub(crate) fn attr(&mut self, ty: u32, payload: &[u8]) -> &mut Self {
+        let nlattr = sys::nlattr {
+            nla_len: (size_of::<sys::nlattr>() + payload.len()) as _,
+            nla_type: ty as _,
+        };
+        self.0.extend_from_slice(bytes_of(&nlattr));
+        self.push(payload);
+        self
+    }
+
+    pub(crate) fn attr_value<T>(&mut self, ty: u32, value: &T) -> &mut Self {
+        self.attr(ty, bytes_of(value))
+    }
+
+    pub(crate) fn attr_string(&mut self, ty: u32, value: &str) -> &mut Self {
+        let mut payload = value.as_bytes().to_vec();
+        payload.push(0);
+        self.attr(ty, &payload)
+    }
+
+    /// Appends a nested attribute, whose content is appended by `f`.
+    pub(crate) fn nest<F>(&mut self, ty: u32, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut Self),
+    {
+        let start = self.0.len();
+        self.attr(ty | sys::NLA_F_NESTED, &[]);
+        f(self);
+        let len = (self.0.len() - start) as u16;
+        self.0[start..start + 2].copy_from_slice(&len.to_ne_bytes());
+        self
+    }
+
+    pub(crate) fn finish(mut self) -> Vec<u8> {
+        let len = self.0.len() as _;
+        self.header_mut().nlmsg_len = len;
+        self.0
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Message {
+    pub(crate) header: sys::nlmsghdr,
+    pub(crate) payload: Vec<u8>,
+}
+
+impl Message {
+    pub(crate) fn parse(mut buf: &[u8]) -> Vec<Self> {
+        let mut messages = Vec::new();
+        while let Some(header) = read::<sys::nlmsghdr>(buf) {
+            let len = header.nlmsg_len as usize;
+            if len < size_of::<sys::nlmsghdr>() || len > buf.len() {
+                break;
+            }
+            messages.push(Self {
+                header,
+                payload: buf[size_of::<sys::nlmsghdr>()..len].to_vec(),
+            });
+            buf = &buf[align(len).min(buf.len())..];
+        }
+        messages
+    }
+
+    pub(crate) fn ty(&self) -> u32 {
+        self.header.nlmsg_type as _
+    }
+
+    /// Returns the family header of the payload.
+    pub(crate) fn family_header<T: Copy>(&self) -> Option<T> {
+        read(&self.payload)
+    }
+
+    /// Returns the attributes following the family header `T`.
+    pub(crate) fn attrs<T>(&self) -> Attrs<'_> {
+        Attrs(
+            self.payload
+                .get(align(size_of::<T>())..)
+                .unwrap_or_default(),
+        )
+    }
+
+    // None for an acknowledgement
+    fn error(&self) -> Option<Error> {
+        let nlmsgerr = read::<sys::nlmsgerr>(&self.payload)?;
+        if nlmsgerr.error == 0 {
+            return None;
+        }
+        let mut message = None;
+        if self.header.nlmsg_flags as u32 & sys::NLM_F_ACK_TLVS != 0 {
+            // the request is echoed unless capped
+            let offset = if self.header.nlmsg_flags as u32 & sys::NLM_F_CAPPED != 0 {
+                size_of::<sys::nlmsgerr>()
+            } else {
+                size_of::<c_int>() + nlmsgerr.msg.nlmsg_len as usize
+            };
+            for (ty, payload) in Attrs(self.payload.get(align(offset)..).unwrap_or_default()) {
+                if ty == sys::NLMSGERR_ATTR_MSG {
+                    message = Some(read_string(payload));
+                }
+            }
+        }
+        Some(Error::Netlink {
+            errno: -nlmsgerr.error,
+            message,
+        })
+    }
+}
+
+/// Iterator over netlink attributes as (type, payload).
+#[derive(Clone)]
+pub(crate) struct Attrs<'a>(pub(crate) &'a [u8]);
+
+impl<'a> Iterator for Attrs<'a> {
+    type Item = (u32, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let nlattr = read::<sys::nlattr>(self.0)?;
+        let len = nlattr.nla_len as usize;
+        if len < size_of::<sys::nlattr>() || len > self.0.len() {
+            self.0 = &[];
+            return None;
+        }
+        let payload = &self.0[size_of::<sys::nlattr>()..len];
+        self.0 = &self.0[align(len).min(self.0.len())..];
+        Some((
+            (nlattr.nla_type & !(sys::NLA_F_NESTED | sys::NLA_F_NET_BYTEORDER) as u16) as _,
+            payload,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,129 @@
+use super::{align, bytes_of, read_string, Attrs, Message, MessageBuilder};
+use crate::{sys, Error};
+use std::mem::{size_of, MaybeUninit};
+
+fn nlmsghdr(ty: u32, flags: u32, seq: u32, payload_len: usize) -> sys::nlmsghdr {
+    let mut header = unsafe { MaybeUninit::<sys::nlmsghdr>::zeroed().assume_init() };
+    header.nlmsg_len = (size_of::<sys::nlmsghdr>() + payload_len) as _;
+    header.nlmsg_type = ty as _;
+    header.nlmsg_flags = flags as _;
+    header.nlmsg_seq = seq;
+    header
+}
+
+fn nlmsgerr(error: i32, flags: u32, attrs: &[u8]) -> Vec<u8> {
+    let mut nlmsgerr = unsafe { MaybeUninit::<sys::nlmsgerr>::zeroed().assume_init() };
+    nlmsgerr.error = error;
+    nlmsgerr.msg = nlmsghdr(sys::RTM_NEWLINK, 0, 1, 0);
+    let mut payload = bytes_of(&nlmsgerr).to_vec();
+    payload.extend_from_slice(attrs);
+    let mut buf = bytes_of(&nlmsghdr(sys::NLMSG_ERROR, flags, 1, payload.len())).to_vec();
+    buf.extend_from_slice(&payload);
+    buf
+}
+
+#[test]
+fn test_align() {
+    assert_eq!(align(0), 0);
+    assert_eq!(align(1), 4);
+    assert_eq!(align(4), 4);
+    assert_eq!(align(5), 8);
+}
+
+#[test]
+fn test_read_string() {
+    assert_eq!(read_string(b"vcan0\0\0\0"), "vcan0");
+    assert_eq!(read_string(b"vcan0"), "vcan0");
+}
+
+#[test]
+fn test_builder() {
+    let mut message = MessageBuilder::new(sys::RTM_GETLINK, sys::NLM_F_DUMP, &[0_u8; 1]);
+    message.attr_value(1, &42_u32).attr(2, b"can0\0");
+    let buf = message.finish();
+    assert_eq!(buf.len(), 16 + 4 + 8 + 12);
+
+    let messages = Message::parse(&buf);
+    assert_eq!(messages.len(), 1);
+    let message = &messages[0];
+    assert_eq!(message.header.nlmsg_len as usize, buf.len());
+    assert_eq!(message.ty(), sys::RTM_GETLINK);
+    assert_eq!(
+        message.header.nlmsg_flags as u32,
+        sys::NLM_F_REQUEST | sys::NLM_F_DUMP
+    );
+    let attrs = message.attrs::<u8>().collect::<Vec<_>>();
+    assert_eq!(
+        attrs,
+        vec![(1, &42_u32.to_ne_bytes()[..]), (2, &b"can0\0"[..])]
+    );
+}
+
+#[test]
+fn test_nest() {
+    let mut message = MessageBuilder::new(sys::RTM_NEWLINK, 0, &0_u32);
+    message.nest(1, |message| {
+        message.attr(2, &[1, 2, 3]).attr_string(3, "can");
+    });
+    let messages = Message::parse(&message.finish());
+    let (ty, payload) = messages[0].attrs::<u32>().next().unwrap();
+    assert_eq!(ty, 1);
+    assert_eq!(payload.len(), 8 + 8);
+    let nested = Attrs(payload).collect::<Vec<_>>();
+    assert_eq!(nested, vec![(2, &[1, 2, 3][..]), (3, &b"can\0"[..])]);
+}
+
+#[test]
+fn test_parse_multiple() {
+    let mut buf = Vec::new();
+    for seq in 0..3 {
+        buf.extend_from_slice(bytes_of(&nlmsghdr(sys::RTM_NEWLINK, 0, seq, 3)));
+        buf.extend_from_slice(&[0xff; 4]);
+    }
+    let messages = Message::parse(&buf);
+    assert_eq!(messages.len(), 3);
+    for (seq, message) in messages.iter().enumerate() {
+        assert_eq!(message.header.nlmsg_seq, seq as u32);
+        assert_eq!(message.payload, [0xff; 3]);
+    }
+
+    // truncated
+    assert_eq!(Message::parse(&buf[..buf.len() - 8]).len(), 2);
+}
+
+#[test]
+fn test_attrs_malformed() {
+    assert_eq!(Attrs(&[8, 0, 1, 0, 0]).count(), 0);
+    assert_eq!(Attrs(&[2, 0, 1, 0]).count(), 0);
+}
+
+#[test]
+fn test_ack() {
+    let messages = Message::parse(&nlmsgerr(0, 0, &[]));
+    assert!(messages[0].error().is_none());
+}
+
+#[test]
+fn test_error() {
+    let messages = Message::parse(&nlmsgerr(-libc::EINVAL, 0, &[]));
+    assert!(matches!(
+        messages[0].error(),
+        Some(Error::Netlink {
+            errno: libc::EINVAL,
+            message: None
+        })
+    ));
+
+    let mut attr = MessageBuilder::new(0, 0, &());
+    attr.attr(sys::NLMSGERR_ATTR_MSG, b"unknown device\0");
+    let attrs = attr.finish()[size_of::<sys::nlmsghdr>()..].to_vec();
+    let flags = sys::NLM_F_ACK_TLVS | sys::NLM_F_CAPPED;
+    let messages = Message::parse(&nlmsgerr(-libc::ENODEV, flags, &attrs));
+    match messages[0].error() {
+        Some(Error::Netlink { errno, message }) => {
+            assert_eq!(errno, libc::ENODEV);
+            assert_eq!(message.as_deref(), Some("unknown device"));
+        }
+        e => panic!("{:?}", e),
+    }
+}
@@ -0,0 +1,41 @@
+use crate::{sys, Cmsg, CmsgIter, Frame, MsgFlags};
+
+/// A frame with the metadata of its reception.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug)]
+pub struct ReceivedFrame {
+    pub frame: Frame,
+    /// Available if enabled by `set_timestamping`.
+    pub timestamps: Option<[libc::timespec; 3]>,
+    /// Index of the interface the frame was received on.
+    pub ifindex: u32,
+    pub flags: MsgFlags,
+    /// Number of frames dropped by the socket since it was created.
+    /// Available if enabled by `set_rxq_ovfl`.
+    pub dropped: Option<u32>,
+}
+
+impl ReceivedFrame {
+    pub(crate) fn new(
+        frame: Frame,
+        address: &sys::sockaddr_can,
+        flags: i32,
+        cmsgs: CmsgIter<'_>,
+    ) -> Self {
+        let mut received = Self {
+            frame,
+            timestamps: None,
+            ifindex: address.can_ifindex as _,
+            flags: MsgFlags::from_bits_truncate(flags),
+            dropped: None,
+        };
+        for cmsg in cmsgs {
+            match cmsg {
+                Cmsg::Timestamping(timestamps) => received.timestamps = Some(*timestamps),
+                Cmsg::RxqOvfl(dropped) => received.dropped = Some(*dropped),
+                _ => (),
+            }
+        }
+        received
+    }
+}
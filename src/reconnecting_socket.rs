@@ -0,0 +1,339 @@
+use crate::{
+    Error, Frame, InterfaceEvent, InterfaceWatcher, ReceivedFrame, Result, Socket, Timestamping,
+};
+use std::ffi::{CStr, CString};
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+pub enum SocketEvent {
+    Frame(ReceivedFrame),
+    /// The interface disappeared. The socket waits for it to return.
+    Disconnected,
+    /// The interface returned and the socket was bound to it again, possibly with another index.
+    /// Frames on the bus during `gap` were not received.
+    Reconnected {
+        ifindex: u32,
+        gap: Duration,
+    },
+}
+
+/// A [`Socket`] that survives its interface being removed, e.g. a USB adapter being unplugged.
+///
+/// When the interface disappears, the socket waits for an interface of the same name,
+/// binds to it and re-applies the options set through this wrapper.
+/// [`recv`](Self::recv) reports the transitions as [`SocketEvent`]s
+/// and [`send`](Self::send) fails with [`Error::InterfaceNotFound`] in the meantime.
+pub struct ReconnectingSocket(Reconnecting<Socket>);
+
+impl ReconnectingSocket {
+    /// Succeeds even if the interface does not exist yet, waiting for it on [`recv`](Self::recv).
+    pub fn bind<I>(ifname: I) -> Result<Self>
+    where
+        I: AsRef<CStr>,
+    {
+        Reconnecting::bind(ifname).map(Self)
+    }
+
+    pub fn ifname(&self) -> &CStr {
+        &self.0.ifname
+    }
+
+    /// Returns the socket bound to the interface, or `None` while disconnected.
+    pub fn socket(&self) -> Option<&Socket> {
+        self.0.socket()
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.0.socket().is_some()
+    }
+
+    pub fn set_timestamping(&mut self, timestamping: Timestamping) -> Result<()> {
+        self.0.set_timestamping(timestamping)
+    }
+
+    pub fn set_recv_own_msgs(&mut self, enable: bool) -> Result<()> {
+        self.0.set_recv_own_msgs(enable)
+    }
+
+    pub fn set_fd_frames(&mut self, enable: bool) -> Result<()> {
+        self.0.set_fd_frames(enable)
+    }
+
+    /// Enables CAN FD frames if the interface supports them, again on every reconnection.
+    /// Returns whether CAN FD frames are enabled, `false` while disconnected.
+    pub fn set_fd_frames_if_supported(&mut self) -> Result<bool> {
+        self.0.set_fd_frames_if_supported()
+    }
+
+    pub fn set_rxq_ovfl(&mut self, enable: bool) -> Result<()> {
+        self.0.set_rxq_ovfl(enable)
+    }
+
+    /// Receives a frame, or blocks until the interface returns while disconnected.
+    pub fn recv(&mut self) -> Result<SocketEvent> {
+        loop {
+            let inner = &mut self.0;
+            let event = match &mut inner.state {
+                State::Connected(socket) => {
+                    let result = socket.recv_frame();
+                    inner.received(result)?
+                }
+                State::Disconnected { reported, .. } if !*reported => {
+                    *reported = true;
+                    Some(SocketEvent::Disconnected)
+                }
+                State::Disconnected { since, watcher, .. } => {
+                    let watcher = match watcher {
+                        Some(watcher) => watcher,
+                        // subscribe before binding not to miss the interface in between
+                        None => watcher.insert(InterfaceWatcher::new()?),
+                    };
+                    let since = *since;
+                    match inner.options.bind(&inner.ifname)? {
+                        Some(socket) => Some(inner.reconnected(socket, since)?),
+                        None => {
+                            wait(watcher, &inner.ifname)?;
+                            None
+                        }
+                    }
+                }
+            };
+            if let Some(event) = event {
+                return Ok(event);
+            }
+        }
+    }
+
+    /// Fails with [`Error::InterfaceNotFound`] while disconnected.
+    pub fn send(&mut self, frame: &Frame) -> Result<()> {
+        let result = self.0.socket().map(|socket| socket.send(frame));
+        self.0.sent(result)
+    }
+}
+
+// a socket bound by the blocking or the asynchronous reconnecting socket
+pub(crate) trait Connection: Sized {
+    type Watcher;
+
+    fn connect(socket: Socket) -> Result<Self>;
+
+    fn get(&self) -> &Socket;
+}
+
+impl Connection for Socket {
+    type Watcher = InterfaceWatcher;
+
+    fn connect(socket: Socket) -> Result<Self> {
+        Ok(socket)
+    }
+
+    fn get(&self) -> &Socket {
+        self
+    }
+}
+
+// the state and the options shared by the blocking and the asynchronous reconnecting socket,
+// which implement receiving and sending on top
+pub(crate) struct Reconnecting<S: Connection> {
+    pub(crate) ifname: CString,
+    pub(crate) options: Options,
+    pub(crate) state: State<S>,
+}
+
+pub(crate) enum State<S: Connection> {
+    Connected(S),
+    Disconnected {
+        since: Instant,
+        reported: bool,
+        watcher: Option<S::Watcher>,
+    },
+}
+
+impl<S: Connection> Reconnecting<S> {
+    pub(crate) fn bind<I>(ifname: I) -> Result<Self>
+    where
+        I: AsRef<CStr>,
+    {
+        let ifname = ifname.as_ref().to_owned();
+        let options = Options::default();
+        let state = match options.bind(&ifname)? {
+            Some(socket) => State::Connected(S::connect(socket)?),
+            None => State::Disconnected {
+                since: Instant::now(),
+                reported: true,
+                watcher: None,
+            },
+        };
+        Ok(Self {
+            ifname,
+            options,
+            state,
+        })
+    }
+
+    pub(crate) fn socket(&self) -> Option<&S> {
+        match &self.state {
+            State::Connected(socket) => Some(socket),
+            State::Disconnected { .. } => None,
+        }
+    }
+
+    pub(crate) fn set_timestamping(&mut self, timestamping: Timestamping) -> Result<()> {
+        self.options.timestamping = Some(timestamping);
+        self.apply(|socket| socket.set_timestamping(timestamping))
+    }
+
+    pub(crate) fn set_recv_own_msgs(&mut self, enable: bool) -> Result<()> {
+        self.options.recv_own_msgs = Some(enable);
+        self.apply(|socket| socket.set_recv_own_msgs(enable))
+    }
+
+    pub(crate) fn set_fd_frames(&mut self, enable: bool) -> Result<()> {
+        self.options.fd_frames = Some(FdFrames::Enabled(enable));
+        self.apply(|socket| socket.set_fd_frames(enable))
+    }
+
+    pub(crate) fn set_fd_frames_if_supported(&mut self) -> Result<bool> {
+        self.options.fd_frames = Some(FdFrames::IfSupported);
+        match self.socket() {
+            Some(socket) => socket.get().set_fd_frames_if_supported(),
+            None => Ok(false),
+        }
+    }
+
+    pub(crate) fn set_rxq_ovfl(&mut self, enable: bool) -> Result<()> {
+        self.options.rxq_ovfl = Some(enable);
+        self.apply(|socket| socket.set_rxq_ovfl(enable))
+    }
+
+    fn apply<F>(&self, f: F) -> Result<()>
+    where
+        F: FnOnce(&Socket) -> Result<()>,
+    {
+        self.socket().map_or(Ok(()), |socket| f(socket.get()))
+    }
+
+    // handles the result of receiving while connected, None to receive again
+    pub(crate) fn received(
+        &mut self,
+        result: Result<ReceivedFrame>,
+    ) -> Result<Option<SocketEvent>> {
+        match result {
+            Ok(frame) => Ok(Some(SocketEvent::Frame(frame))),
+            // the interface went down, but it may come up again
+            Err(e) if e.raw_os_error() == Some(libc::ENETDOWN) => Ok(None),
+            Err(e) if is_disconnected(&e) => {
+                self.disconnect();
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    // switches to the socket bound again to the interface
+    pub(crate) fn reconnected(&mut self, socket: Socket, since: Instant) -> Result<SocketEvent> {
+        let ifindex = socket.ifindex()?;
+        self.state = State::Connected(S::connect(socket)?);
+        Ok(SocketEvent::Reconnected {
+            ifindex,
+            gap: since.elapsed(),
+        })
+    }
+
+    // handles the result of sending, None while disconnected
+    pub(crate) fn sent(&mut self, result: Option<Result<()>>) -> Result<()> {
+        match result {
+            Some(Err(e)) if is_disconnected(&e) => {
+                self.disconnect();
+                Err(self.not_found())
+            }
+            Some(result) => result,
+            None => Err(self.not_found()),
+        }
+    }
+
+    fn disconnect(&mut self) {
+        self.state = State::Disconnected {
+            since: Instant::now(),
+            reported: false,
+            watcher: None,
+        };
+    }
+
+    fn not_found(&self) -> Error {
+        Error::InterfaceNotFound {
+            ifname: self.ifname.to_string_lossy().into_owned(),
+        }
+    }
+}
+
+// waits until an interface of the name may have appeared
+fn wait(watcher: &mut InterfaceWatcher, ifname: &CStr) -> Result<()> {
+    while !appeared(watcher.recv(), ifname)? {}
+    Ok(())
+}
+
+// whether an interface of the name may have appeared
+pub(crate) fn appeared(event: Result<InterfaceEvent>, ifname: &CStr) -> Result<bool> {
+    match event {
+        Ok(InterfaceEvent::Added(interface)) | Ok(InterfaceEvent::Up(interface)) => {
+            Ok(interface.name.as_bytes() == ifname.to_bytes())
+        }
+        Ok(_) => Ok(false),
+        // notifications were dropped, which may include the one for the interface
+        Err(e) if e.raw_os_error() == Some(libc::ENOBUFS) => Ok(true),
+        Err(e) => Err(e),
+    }
+}
+
+// errors of a socket whose interface was removed:
+// ENODEV on receiving, ENXIO on sending
+fn is_disconnected(e: &Error) -> bool {
+    matches!(e.raw_os_error(), Some(libc::ENODEV) | Some(libc::ENXIO))
+}
+
+#[derive(Clone, Copy, Debug)]
+enum FdFrames {
+    Enabled(bool),
+    IfSupported,
+}
+
+// options to re-apply on reconnection
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Options {
+    timestamping: Option<Timestamping>,
+    recv_own_msgs: Option<bool>,
+    fd_frames: Option<FdFrames>,
+    rxq_ovfl: Option<bool>,
+}
+
+impl Options {
+    // binds a socket with the options, None if the interface does not exist
+    pub(crate) fn bind(&self, ifname: &CStr) -> Result<Option<Socket>> {
+        let socket = match Socket::bind(ifname) {
+            Ok(socket) => socket,
+            Err(Error::InterfaceNotFound { .. }) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        if let Some(timestamping) = self.timestamping {
+            socket.set_timestamping(timestamping)?;
+        }
+        if let Some(enable) = self.recv_own_msgs {
+            socket.set_recv_own_msgs(enable)?;
+        }
+        match self.fd_frames {
+            Some(FdFrames::Enabled(enable)) => socket.set_fd_frames(enable)?,
+            Some(FdFrames::IfSupported) => {
+                socket.set_fd_frames_if_supported()?;
+            }
+            None => (),
+        }
+        if let Some(enable) = self.rxq_ovfl {
+            socket.set_rxq_ovfl(enable)?;
+        }
+        Ok(Some(socket))
+    }
+}
+
+#[cfg(test)]
+mod tests;
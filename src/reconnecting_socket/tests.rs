@@ -0,0 +1,86 @@
+use super::{is_disconnected, ReconnectingSocket, SocketEvent};
+use crate::socket::tests::{create_vcan, random_data_standard, random_ifname};
+use crate::Error;
+use std::io;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn test_bind_not_found() {
+    let mut socket = ReconnectingSocket::bind(random_ifname("vcantest")).unwrap();
+    assert!(!socket.is_connected());
+    socket.set_recv_own_msgs(true).unwrap();
+    assert!(!socket.set_fd_frames_if_supported().unwrap());
+    assert!(matches!(
+        socket.send(&random_data_standard()),
+        Err(Error::InterfaceNotFound { .. })
+    ));
+}
+
+#[test]
+fn test_is_disconnected() {
+    assert!(is_disconnected(
+        &io::Error::from_raw_os_error(libc::ENODEV).into()
+    ));
+    assert!(is_disconnected(
+        &io::Error::from_raw_os_error(libc::ENXIO).into()
+    ));
+    assert!(!is_disconnected(
+        &io::Error::from_raw_os_error(libc::ENETDOWN).into()
+    ));
+    assert!(!is_disconnected(&Error::FdFramesDisabled));
+}
+
+#[test]
+#[ignore]
+fn test_reconnect() {
+    let name = random_ifname("vcantest");
+    let vcan = create_vcan(&name);
+    let mut socket = ReconnectingSocket::bind(&name).unwrap();
+    socket.set_recv_own_msgs(true).unwrap();
+    let ifindex = vcan.ifindex();
+
+    vcan.delete().unwrap();
+    assert!(matches!(socket.recv().unwrap(), SocketEvent::Disconnected));
+    assert!(!socket.is_connected());
+
+    let handle = {
+        let name = name.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(100));
+            create_vcan(&name)
+        })
+    };
+    let event = socket.recv().unwrap();
+    let vcan = handle.join().unwrap();
+    match event {
+        SocketEvent::Reconnected { ifindex: i, gap } => {
+            assert_eq!(i, vcan.ifindex());
+            assert_ne!(i, ifindex);
+            assert!(gap >= Duration::from_millis(100));
+        }
+        event => panic!("{:?}", event),
+    }
+
+    // recv_own_msgs is re-applied
+    let frame = random_data_standard();
+    socket.send(&frame).unwrap();
+    match socket.recv().unwrap() {
+        SocketEvent::Frame(received) => assert_eq!(received.frame, frame),
+        event => panic!("{:?}", event),
+    }
+}
+
+#[test]
+#[ignore]
+fn test_send_disconnected() {
+    let name = random_ifname("vcantest");
+    let vcan = create_vcan(&name);
+    let mut socket = ReconnectingSocket::bind(&name).unwrap();
+    vcan.delete().unwrap();
+    assert!(matches!(
+        socket.send(&random_data_standard()),
+        Err(Error::InterfaceNotFound { .. })
+    ));
+    assert!(matches!(socket.recv().unwrap(), SocketEvent::Disconnected));
+}
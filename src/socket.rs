@@ -1,35 +1,31 @@
-use crate::{sys, CmsgIter, Frame, Timestamping};
+use crate::cmsg::CmsgBuf;
+use crate::{sys, CmsgIter, Error, Frame, ReceivedFrame, Result, Timestamping};
 use std::ffi::CStr;
-use std::io::{Error, Result};
 use std::mem::{self, size_of, size_of_val, MaybeUninit};
-use std::os::raw::c_int;
+use std::os::raw::{c_int, c_uint};
 use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
 use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
-pub struct Socket(RawFd);
+pub struct Socket {
+    fd: RawFd,
+    // MTU of the bound interface, 0 if unknown
+    mtu: AtomicUsize,
+}
 
 impl Socket {
     pub fn bind<I>(ifname: I) -> Result<Self>
     where
         I: AsRef<CStr>,
     {
-        let ifindex = unsafe { libc::if_nametoindex(ifname.as_ref().as_ptr()) };
-        if ifindex == 0 {
-            return Err(Error::last_os_error());
-        }
-
-        let fd = unsafe { libc::socket(libc::PF_CAN, libc::SOCK_RAW, sys::CAN_RAW as _) };
-        if fd == -1 {
-            return Err(Error::last_os_error());
-        }
-        let socket = Self(fd);
-
-        let mut address = MaybeUninit::<sys::sockaddr_can>::zeroed();
-        let address = unsafe {
-            (*address.as_mut_ptr()).can_family = libc::AF_CAN as _;
-            (*address.as_mut_ptr()).can_ifindex = ifindex as _;
-            address.assume_init()
+        let ifindex = if_nametoindex(ifname)?;
+        let fd = socket(libc::SOCK_RAW, sys::CAN_RAW as _, "CAN_RAW")?;
+        let socket = Self {
+            fd,
+            mtu: AtomicUsize::new(0),
         };
+
+        let address = address(ifindex);
         if unsafe {
             libc::bind(
                 socket.as_raw_fd(),
@@ -39,9 +35,63 @@ impl Socket {
         } {
             return Err(Error::last_os_error());
         }
+        // leave the MTU unknown rather than failing, as it only serves validation
+        if let Ok(mtu) = socket.query_mtu() {
+            socket.mtu.store(mtu, Ordering::Relaxed);
+        }
         Ok(socket)
     }
 
+    /// Returns the index of the bound interface, 0 if the interface was removed.
+    pub fn ifindex(&self) -> Result<u32> {
+        let mut address = MaybeUninit::<sys::sockaddr_can>::zeroed();
+        let mut len = size_of::<sys::sockaddr_can>() as libc::socklen_t;
+        if unsafe { libc::getsockname(self.as_raw_fd(), address.as_mut_ptr() as _, &mut len) } != 0
+        {
+            return Err(Error::last_os_error());
+        }
+        Ok(unsafe { address.assume_init() }.can_ifindex as _)
+    }
+
+    fn query_mtu(&self) -> Result<usize> {
+        let ifindex = self.ifindex()?;
+        let mut ifreq = MaybeUninit::<libc::ifreq>::zeroed();
+        unsafe {
+            if libc::if_indextoname(ifindex as _, (*ifreq.as_mut_ptr()).ifr_name.as_mut_ptr())
+                .is_null()
+            {
+                return Err(Error::last_os_error());
+            }
+            if libc::ioctl(self.as_raw_fd(), libc::SIOCGIFMTU as _, ifreq.as_mut_ptr()) != 0 {
+                return Err(Error::last_os_error());
+            }
+            Ok(ifreq.assume_init().ifr_ifru.ifru_mtu as _)
+        }
+    }
+
+    /// Returns the MTU of the bound interface.
+    /// It determines which kinds of frames the interface can carry, 0 if it could not be queried.
+    ///
+    /// The MTU is cached since binding. Call [`Socket::refresh_mtu`] after changing it.
+    pub fn mtu(&self) -> usize {
+        self.mtu.load(Ordering::Relaxed)
+    }
+
+    /// Queries the MTU of the bound interface again and returns it.
+    pub fn refresh_mtu(&self) -> Result<usize> {
+        let mtu = self.query_mtu()?;
+        self.mtu.store(mtu, Ordering::Relaxed);
+        Ok(mtu)
+    }
+
+    pub fn supports_fd(&self) -> bool {
+        self.mtu() >= sys::CANFD_MTU
+    }
+
+    pub fn supports_xl(&self) -> bool {
+        self.mtu() >= sys::CANXL_MIN_MTU
+    }
+
     pub fn set_nonblocking(&self, nonblocking: bool) -> Result<()> {
         if unsafe { libc::ioctl(self.as_raw_fd(), libc::FIONBIO, &(nonblocking as c_int)) } != 0 {
             return Err(Error::last_os_error());
@@ -50,17 +100,7 @@ impl Socket {
     }
 
     unsafe fn setsockopt<T>(&self, level: c_int, name: c_int, value: &T) -> Result<()> {
-        if libc::setsockopt(
-            self.as_raw_fd(),
-            level,
-            name,
-            value as *const _ as _,
-            size_of_val(value) as _,
-        ) != 0
-        {
-            return Err(Error::last_os_error());
-        }
-        Ok(())
+        setsockopt(self.as_raw_fd(), level, name, value)
     }
 
     pub fn set_timestamping(&self, timestamping: Timestamping) -> Result<()> {
@@ -93,6 +133,34 @@ impl Socket {
         }
     }
 
+    fn fd_frames(&self) -> Result<bool> {
+        let mut value: c_int = 0;
+        unsafe {
+            getsockopt(
+                self.as_raw_fd(),
+                sys::SOL_CAN_RAW as _,
+                sys::CAN_RAW_FD_FRAMES as _,
+                &mut value,
+            )?
+        };
+        Ok(value != 0)
+    }
+
+    /// Enables CAN FD frames if the interface supports them.
+    /// Returns whether CAN FD frames are enabled.
+    pub fn set_fd_frames_if_supported(&self) -> Result<bool> {
+        let supported = self.supports_fd();
+        if supported {
+            self.set_fd_frames(true)?;
+        }
+        Ok(supported)
+    }
+
+    /// Enables reporting the number of dropped frames in [`ReceivedFrame::dropped`].
+    pub fn set_rxq_ovfl(&self, enable: bool) -> Result<()> {
+        unsafe { self.setsockopt(libc::SOL_SOCKET, libc::SO_RXQ_OVFL, &(enable as c_int)) }
+    }
+
     pub fn recv(&self) -> Result<Frame> {
         let mut frame = MaybeUninit::<sys::canfd_frame>::uninit();
         unsafe {
@@ -106,30 +174,42 @@ impl Socket {
         .ok_or_else(Error::last_os_error)
     }
 
-    pub(crate) fn _recv_msg<'a>(
+    unsafe fn recvmsg(
         &self,
-        cmsg_buf: &'a mut [u8],
-    ) -> std::result::Result<(Frame, Option<CmsgIter<'a>>), (Error, &'a mut [u8])> {
+        cmsg_buf: &mut [u8],
+        address: *mut sys::sockaddr_can,
+    ) -> Result<(Frame, libc::msghdr)> {
         let mut frame = MaybeUninit::<sys::canfd_frame>::uninit();
         let mut iov = MaybeUninit::<libc::iovec>::uninit();
-        let mut msg = MaybeUninit::<libc::msghdr>::uninit();
-        unsafe {
-            (*iov.as_mut_ptr()).iov_base = frame.as_mut_ptr() as _;
-            (*iov.as_mut_ptr()).iov_len = size_of::<sys::canfd_frame>();
+        let mut msg = MaybeUninit::<libc::msghdr>::zeroed();
+        (*iov.as_mut_ptr()).iov_base = frame.as_mut_ptr() as _;
+        (*iov.as_mut_ptr()).iov_len = size_of::<sys::canfd_frame>();
 
-            (*msg.as_mut_ptr()).msg_name = ptr::null_mut();
-            (*msg.as_mut_ptr()).msg_iov = iov.as_mut_ptr();
-            (*msg.as_mut_ptr()).msg_iovlen = 1;
-            (*msg.as_mut_ptr()).msg_control = cmsg_buf.as_mut_ptr() as _;
-            (*msg.as_mut_ptr()).msg_controllen = cmsg_buf.len() as _;
+        (*msg.as_mut_ptr()).msg_name = address as _;
+        (*msg.as_mut_ptr()).msg_namelen = if address.is_null() {
+            0
+        } else {
+            size_of::<sys::sockaddr_can>() as _
+        };
+        (*msg.as_mut_ptr()).msg_iov = iov.as_mut_ptr();
+        (*msg.as_mut_ptr()).msg_iovlen = 1;
+        (*msg.as_mut_ptr()).msg_control = cmsg_buf.as_mut_ptr() as _;
+        (*msg.as_mut_ptr()).msg_controllen = cmsg_buf.len() as _;
+
+        let size = libc::recvmsg(self.as_raw_fd(), msg.as_mut_ptr(), 0);
+        // frame will be moved
+        (*iov.as_mut_ptr()).iov_base = ptr::null_mut();
+        let frame = Frame::from_raw(frame, size as _).ok_or_else(Error::last_os_error)?;
+        Ok((frame, msg.assume_init()))
+    }
 
-            let size = libc::recvmsg(self.as_raw_fd(), msg.as_mut_ptr(), 0);
-            // frame will be moved
-            (*iov.as_mut_ptr()).iov_base = ptr::null_mut();
-            let frame = Frame::from_raw(frame, size as _)
-                .ok_or_else(|| (Error::last_os_error(), cmsg_buf))?;
-            let cmsgs = CmsgIter::from_raw(msg.assume_init());
-            Ok((frame, cmsgs))
+    pub(crate) fn _recv_msg<'a>(
+        &self,
+        cmsg_buf: &'a mut [u8],
+    ) -> std::result::Result<(Frame, Option<CmsgIter<'a>>), (Error, &'a mut [u8])> {
+        match unsafe { self.recvmsg(cmsg_buf, ptr::null_mut()) } {
+            Ok((frame, msg)) => Ok((frame, unsafe { CmsgIter::from_raw(msg) })),
+            Err(e) => Err((e, cmsg_buf)),
         }
     }
 
@@ -137,14 +217,63 @@ impl Socket {
         self._recv_msg(cmsg_buf).map_err(|(e, _)| e)
     }
 
+    /// Receives a frame with its metadata.
+    /// Unlike [`recv_msg`](Self::recv_msg), the buffer for control messages is managed internally.
+    pub fn recv_frame(&self) -> Result<ReceivedFrame> {
+        let mut cmsg_buf = CmsgBuf::new();
+        let mut address = MaybeUninit::<sys::sockaddr_can>::zeroed();
+        unsafe {
+            let (frame, msg) = self.recvmsg(&mut cmsg_buf.0, address.as_mut_ptr())?;
+            let cmsgs = CmsgIter::from_raw(msg).ok_or(Error::TruncatedControlData)?;
+            Ok(ReceivedFrame::new(
+                frame,
+                &address.assume_init(),
+                msg.msg_flags,
+                cmsgs,
+            ))
+        }
+    }
+
+    /// Fails with [`Error::MtuMismatch`] or [`Error::FdFramesDisabled`]
+    /// if the frame cannot be sent on this socket.
+    ///
+    /// CAN FD frames are checked against the cached MTU.
+    /// If the kernel rejects one nevertheless, the MTU is re-validated,
+    /// as it may have changed since binding.
     pub fn send(&self, frame: &Frame) -> Result<()> {
+        if let Frame::FdData(_) = frame {
+            // skip the check if the MTU is unknown
+            if self.mtu() != 0 && !self.supports_fd() {
+                return Err(self.fd_mtu_mismatch());
+            }
+        }
         if unsafe { libc::write(self.as_raw_fd(), frame.as_ptr(), frame.size()) } as usize
             != frame.size()
         {
-            return Err(Error::last_os_error());
+            let e = Error::last_os_error();
+            if let (Frame::FdData(_), Error::Io(io)) = (frame, &e) {
+                if io.raw_os_error() == Some(libc::EINVAL) {
+                    if let Ok(mtu) = self.refresh_mtu() {
+                        if mtu < sys::CANFD_MTU {
+                            return Err(self.fd_mtu_mismatch());
+                        }
+                    }
+                    if let Ok(false) = self.fd_frames() {
+                        return Err(Error::FdFramesDisabled);
+                    }
+                }
+            }
+            return Err(e);
         }
         Ok(())
     }
+
+    fn fd_mtu_mismatch(&self) -> Error {
+        Error::MtuMismatch {
+            mtu: self.mtu(),
+            required: sys::CANFD_MTU,
+        }
+    }
 }
 
 impl Drop for Socket {
@@ -155,23 +284,102 @@ impl Drop for Socket {
 
 impl AsRawFd for Socket {
     fn as_raw_fd(&self) -> RawFd {
-        self.0
+        self.fd
     }
 }
 
 impl FromRawFd for Socket {
+    /// The MTU is queried from the bound interface if possible.
     unsafe fn from_raw_fd(fd: RawFd) -> Self {
-        Self(fd)
+        let socket = Self {
+            fd,
+            mtu: AtomicUsize::new(0),
+        };
+        if let Ok(mtu) = socket.query_mtu() {
+            socket.mtu.store(mtu, Ordering::Relaxed);
+        }
+        socket
     }
 }
 
 impl IntoRawFd for Socket {
     fn into_raw_fd(self) -> RawFd {
-        let fd = self.0;
+        let fd = self.fd;
         mem::forget(self);
         fd
     }
 }
 
+pub(crate) fn if_nametoindex<I>(ifname: I) -> Result<c_uint>
+where
+    I: AsRef<CStr>,
+{
+    let ifindex = unsafe { libc::if_nametoindex(ifname.as_ref().as_ptr()) };
+    if ifindex == 0 {
+        return Err(match Error::last_os_error() {
+            Error::Io(e) if e.raw_os_error() == Some(libc::ENODEV) => Error::InterfaceNotFound {
+                ifname: ifname.as_ref().to_string_lossy().into_owned(),
+            },
+            e => e,
+        });
+    }
+    Ok(ifindex)
+}
+
+pub(crate) fn address(ifindex: c_uint) -> sys::sockaddr_can {
+    let mut address = MaybeUninit::<sys::sockaddr_can>::zeroed();
+    unsafe {
+        (*address.as_mut_ptr()).can_family = libc::AF_CAN as _;
+        (*address.as_mut_ptr()).can_ifindex = ifindex as _;
+        address.assume_init()
+    }
+}
+
+pub(crate) unsafe fn setsockopt<T>(fd: RawFd, level: c_int, name: c_int, value: &T) -> Result<()> {
+    if libc::setsockopt(
+        fd,
+        level,
+        name,
+        value as *const _ as _,
+        size_of_val(value) as _,
+    ) != 0
+    {
+        return Err(Error::last_os_error());
+    }
+    Ok(())
+}
+
+pub(crate) unsafe fn getsockopt<T>(
+    fd: RawFd,
+    level: c_int,
+    name: c_int,
+    value: &mut T,
+) -> Result<()> {
+    let mut len = size_of_val(value) as libc::socklen_t;
+    if libc::getsockopt(fd, level, name, value as *mut _ as _, &mut len) != 0 {
+        return Err(Error::last_os_error());
+    }
+    Ok(())
+}
+
+// opens a socket of PF_CAN, naming the protocol in the error
+pub(crate) fn socket(ty: c_int, protocol: c_int, name: &'static str) -> Result<RawFd> {
+    let fd = unsafe { libc::socket(libc::PF_CAN, ty, protocol) };
+    if fd == -1 {
+        return Err(match Error::last_os_error() {
+            Error::Io(e)
+                if matches!(
+                    e.raw_os_error(),
+                    Some(libc::EAFNOSUPPORT | libc::EPROTONOSUPPORT)
+                ) =>
+            {
+                Error::ProtocolNotSupported { protocol: name }
+            }
+            e => e,
+        });
+    }
+    Ok(fd)
+}
+
 #[cfg(test)]
 pub(crate) mod tests;
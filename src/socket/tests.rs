@@ -1,12 +1,16 @@
 use super::Socket;
-use crate::{sys, Cmsg, DataFrame, FdDataFrame, Frame, Id, Timestamping};
+use crate::{
+    sys, CanLink, Cmsg, DataFrame, FdDataFrame, Frame, Id, MsgFlags, MtuClass, ReceivedFrame,
+    Timestamping, VirtualInterface,
+};
 use rand::Rng;
 use spin::RwLock;
 use std::env;
-use std::ffi::CString;
+use std::ffi::{CStr, CString};
 use std::io::ErrorKind;
 use std::io::Result;
 use std::os::unix::ffi::OsStrExt;
+use std::os::unix::io::{FromRawFd, IntoRawFd};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::thread;
@@ -19,6 +23,18 @@ pub(crate) fn ifname() -> CString {
     CString::new(ifname.as_bytes()).unwrap()
 }
 
+// a name for a temporary interface, e.g. "vcantest1a2b"
+pub(crate) fn random_ifname(prefix: &str) -> CString {
+    CString::new(format!("{}{:04x}", prefix, rand::random::<u16>())).unwrap()
+}
+
+// creates a classic vcan interface and brings it up
+pub(crate) fn create_vcan(name: &CStr) -> VirtualInterface {
+    let vcan = VirtualInterface::vcan(name, MtuClass::Classic).unwrap();
+    CanLink::new(name).unwrap().set_up(true).unwrap();
+    vcan
+}
+
 macro_rules! lock {
     (shared) => {
         let _lock = LOCK.read();
@@ -28,7 +44,7 @@ macro_rules! lock {
     };
 }
 
-fn timeout<F, T>(f: F) -> Option<T>
+pub(crate) fn timeout<F, T>(f: F) -> Option<T>
 where
     F: 'static + Send + FnOnce() -> T,
     T: 'static + Send,
@@ -80,6 +96,19 @@ fn recv_msg(socket: Socket, query: Option<Frame>) -> Option<Result<Option<libc::
     })
 }
 
+fn recv_frame(socket: Socket, query: Option<Frame>) -> Option<Result<ReceivedFrame>> {
+    timeout(move || loop {
+        let received = socket.recv_frame()?;
+        if query
+            .as_ref()
+            .map(|query| &received.frame == query)
+            .unwrap_or(true)
+        {
+            return Ok(received);
+        }
+    })
+}
+
 pub(crate) fn random_data_standard() -> Frame {
     let mut rng = rand::thread_rng();
     let id = Id::Standard(rng.gen_range(0..sys::CAN_SFF_MASK));
@@ -213,10 +242,9 @@ fn test_default_fd_frames_off() {
     let socket = Socket::bind(ifname()).unwrap();
 
     let frame = random_fd_data_standard();
-    assert_eq!(
-        socket.send(&frame).unwrap_err().kind(),
-        ErrorKind::InvalidInput
-    );
+    let e = socket.send(&frame).unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::InvalidInput);
+    assert!(e.to_string().contains("set_fd_frames"));
 }
 
 #[test]
@@ -232,3 +260,71 @@ fn test_set_fd_frames_on() {
     socket_tx.send(&frame).unwrap();
     recv(socket_rx, Some(frame)).unwrap().unwrap();
 }
+
+#[test]
+#[ignore]
+fn test_recv_frame_default() {
+    lock!(shared);
+    let socket_tx = Socket::bind(ifname()).unwrap();
+    let socket_rx = Socket::bind(ifname()).unwrap();
+
+    let frame = random_data_standard();
+    socket_tx.send(&frame).unwrap();
+    let received = recv_frame(socket_rx, Some(frame)).unwrap().unwrap();
+    assert_eq!(received.ifindex, unsafe {
+        libc::if_nametoindex(ifname().as_ptr())
+    });
+    assert_eq!(received.flags, MsgFlags::DONTROUTE);
+    assert!(received.timestamps.is_none());
+    assert!(received.dropped.is_none());
+}
+
+#[test]
+#[ignore]
+fn test_recv_frame_enabled() {
+    lock!(shared);
+    let socket = Socket::bind(ifname()).unwrap();
+    socket.set_recv_own_msgs(true).unwrap();
+    socket
+        .set_timestamping(Timestamping::RX_SOFTWARE | Timestamping::SOFTWARE)
+        .unwrap();
+    socket.set_rxq_ovfl(true).unwrap();
+
+    let frame = random_data_standard();
+    socket.send(&frame).unwrap();
+    let received = recv_frame(socket, Some(frame)).unwrap().unwrap();
+    assert_eq!(received.flags, MsgFlags::DONTROUTE | MsgFlags::CONFIRM);
+    assert!(received.timestamps.unwrap()[0].tv_sec != 0);
+    assert_eq!(received.dropped, Some(0));
+}
+
+#[test]
+fn test_cmsg_space() {
+    assert!(Cmsg::space() <= crate::cmsg::CmsgBuf::new().0.len());
+}
+
+#[test]
+#[ignore]
+fn test_mtu() {
+    lock!(shared);
+    let socket = Socket::bind(ifname()).unwrap();
+    assert!(socket.mtu() >= sys::CAN_MTU);
+    assert_eq!(socket.supports_fd(), socket.mtu() >= sys::CANFD_MTU);
+
+    let socket = unsafe { Socket::from_raw_fd(socket.into_raw_fd()) };
+    assert_eq!(socket.supports_fd(), socket.mtu() >= sys::CANFD_MTU);
+}
+
+#[test]
+#[ignore]
+fn test_set_fd_frames_if_supported() {
+    lock!(shared);
+    let socket_tx = Socket::bind(ifname()).unwrap();
+    let socket_rx = Socket::bind(ifname()).unwrap();
+    assert!(socket_tx.set_fd_frames_if_supported().unwrap());
+    assert!(socket_rx.set_fd_frames_if_supported().unwrap());
+
+    let frame = random_fd_data_standard();
+    socket_tx.send(&frame).unwrap();
+    recv(socket_rx, Some(frame)).unwrap().unwrap();
+}
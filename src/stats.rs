@@ -0,0 +1,226 @@
+use crate::{Error, Result};
+use std::collections::HashMap;
+use std::ffi::CStr;
+use std::fs;
+use std::io;
+
+/// Statistics of the CAN core (`/proc/net/can/stats`).
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CanStats {
+    pub tx_frames: u64,
+    pub rx_frames: u64,
+    /// Received frames which matched a receive list entry.
+    pub matches: u64,
+    /// `None` if the kernel was loaded with `stats_timer=0`.
+    pub rates: Option<CanRates>,
+    pub rcv_entries: u64,
+    pub rcv_entries_max: u64,
+    pub stats_reset: u64,
+    pub user_reset: u64,
+}
+
+/// Match ratios in percent and rates in frames per second, updated every second.
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CanRates {
+    pub total_rx_match_ratio: u64,
+    pub total_tx_rate: u64,
+    pub total_rx_rate: u64,
+    pub current_rx_match_ratio: u64,
+    pub current_tx_rate: u64,
+    pub current_rx_rate: u64,
+    pub max_rx_match_ratio: u64,
+    pub max_tx_rate: u64,
+    pub max_rx_rate: u64,
+}
+
+/// The receive lists of the CAN core, where protocols register their filters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ReceiveList {
+    /// Filters matching all frames.
+    All,
+    /// Filters with a mask.
+    Fil,
+    /// Inverted filters (`CAN_INV_FILTER`).
+    Inv,
+    /// Filters of single standard IDs.
+    Sff,
+    /// Filters of single extended IDs.
+    Eff,
+    /// Error frame masks.
+    Err,
+}
+
+impl ReceiveList {
+    fn path(self) -> &'static str {
+        match self {
+            Self::All => "/proc/net/can/rcvlist_all",
+            Self::Fil => "/proc/net/can/rcvlist_fil",
+            Self::Inv => "/proc/net/can/rcvlist_inv",
+            Self::Sff => "/proc/net/can/rcvlist_sff",
+            Self::Eff => "/proc/net/can/rcvlist_eff",
+            Self::Err => "/proc/net/can/rcvlist_err",
+        }
+    }
+}
+
+/// A filter registered in a receive list.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReceiveListEntry {
+    /// `None` for filters on all interfaces.
+    pub device: Option<String>,
+    pub can_id: u32,
+    pub can_mask: u32,
+    /// Address of the receive function,
+    /// hashed or zero depending on `kernel.kptr_restrict`.
+    pub function: u64,
+    /// Address of the socket for most protocols,
+    /// hashed or zero depending on `kernel.kptr_restrict`.
+    pub userdata: u64,
+    pub matches: u64,
+    /// The protocol which registered the filter, e.g. `raw` or `bcm`.
+    pub ident: String,
+}
+
+/// Counters of a network interface (`/sys/class/net/<ifname>/statistics`).
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LinkStats {
+    pub rx_packets: u64,
+    pub tx_packets: u64,
+    pub rx_bytes: u64,
+    pub tx_bytes: u64,
+    pub rx_errors: u64,
+    pub tx_errors: u64,
+    pub rx_dropped: u64,
+    pub tx_dropped: u64,
+    /// Frames lost because of a controller overrun.
+    pub rx_over_errors: u64,
+    pub rx_fifo_errors: u64,
+    pub tx_aborted_errors: u64,
+}
+
+/// Fails with [`Error::ProtocolNotSupported`] if the CAN core (`can`) is not loaded.
+pub fn can_stats() -> Result<CanStats> {
+    parse_stats(&read_proc("/proc/net/can/stats")?).ok_or_else(invalid_data)
+}
+
+/// Lists the filters of all interfaces registered in the receive list.
+pub fn receive_list(list: ReceiveList) -> Result<Vec<ReceiveListEntry>> {
+    Ok(parse_receive_list(&read_proc(list.path())?))
+}
+
+pub fn link_stats<I>(ifname: I) -> Result<LinkStats>
+where
+    I: AsRef<CStr>,
+{
+    let ifname = ifname.as_ref().to_string_lossy();
+    let read = |name: &str| -> Result<u64> {
+        let path = format!("/sys/class/net/{}/statistics/{}", ifname, name);
+        match fs::read_to_string(path) {
+            Ok(value) => value.trim().parse().map_err(|_| invalid_data()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Err(Error::InterfaceNotFound {
+                ifname: ifname.clone().into_owned(),
+            }),
+            Err(e) => Err(e.into()),
+        }
+    };
+    Ok(LinkStats {
+        rx_packets: read("rx_packets")?,
+        tx_packets: read("tx_packets")?,
+        rx_bytes: read("rx_bytes")?,
+        tx_bytes: read("tx_bytes")?,
+        rx_errors: read("rx_errors")?,
+        tx_errors: read("tx_errors")?,
+        rx_dropped: read("rx_dropped")?,
+        tx_dropped: read("tx_dropped")?,
+        rx_over_errors: read("rx_over_errors")?,
+        rx_fifo_errors: read("rx_fifo_errors")?,
+        tx_aborted_errors: read("tx_aborted_errors")?,
+    })
+}
+
+fn read_proc(path: &str) -> Result<String> {
+    fs::read_to_string(path).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => Error::ProtocolNotSupported { protocol: "CAN" },
+        _ => e.into(),
+    })
+}
+
+fn invalid_data() -> Error {
+    io::Error::new(io::ErrorKind::InvalidData, "unexpected format").into()
+}
+
+// lines of " %8ld <description> (<tag>)"
+fn parse_stats(s: &str) -> Option<CanStats> {
+    let values = s
+        .lines()
+        .filter_map(|line| {
+            let value = line.split_whitespace().next()?.parse::<u64>().ok()?;
+            let tag = line.trim_end().strip_suffix(')')?.rsplit('(').next()?;
+            Some((tag, value))
+        })
+        .collect::<HashMap<_, _>>();
+    let rates = if values.contains_key("RXMR") {
+        Some(CanRates {
+            total_rx_match_ratio: values["RXMR"],
+            total_tx_rate: *values.get("TXR")?,
+            total_rx_rate: *values.get("RXR")?,
+            current_rx_match_ratio: *values.get("CRXMR")?,
+            current_tx_rate: *values.get("CTXR")?,
+            current_rx_rate: *values.get("CRXR")?,
+            max_rx_match_ratio: *values.get("MRXMR")?,
+            max_tx_rate: *values.get("MTXR")?,
+            max_rx_rate: *values.get("MRXR")?,
+        })
+    } else {
+        None
+    };
+    Some(CanStats {
+        tx_frames: *values.get("TXF")?,
+        rx_frames: *values.get("RXF")?,
+        matches: *values.get("RXMF")?,
+        rates,
+        rcv_entries: *values.get("CRCV")?,
+        rcv_entries_max: *values.get("MRCV")?,
+        // printed only if nonzero
+        stats_reset: values.get("STR").copied().unwrap_or_default(),
+        user_reset: values.get("USTR").copied().unwrap_or_default(),
+    })
+}
+
+// entries of "   <device>  <can_id>  <can_mask>  <function>  <userdata>  <matches>  <ident>",
+// skipping the headings, banners and "(<device>: no entry)"
+fn parse_receive_list(s: &str) -> Vec<ReceiveListEntry> {
+    s.lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let device = fields.next()?;
+            let hex = |field: Option<&str>| u64::from_str_radix(field?, 16).ok();
+            let can_id = hex(fields.next())? as u32;
+            let can_mask = hex(fields.next())? as u32;
+            let function = hex(fields.next())?;
+            let userdata = hex(fields.next())?;
+            let matches = fields.next()?.parse().ok()?;
+            let ident = fields.next()?.to_owned();
+            Some(ReceiveListEntry {
+                device: if device == "any" {
+                    None
+                } else {
+                    Some(device.to_owned())
+                },
+                can_id,
+                can_mask,
+                function,
+                userdata,
+                matches,
+                ident,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests;
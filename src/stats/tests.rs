@@ -0,0 +1,150 @@
+use super::{
+    can_stats, link_stats, parse_receive_list, parse_stats, receive_list, CanRates, CanStats,
+    ReceiveList, ReceiveListEntry,
+};
+use crate::socket::tests::{ifname, LOCK};
+use crate::{sys, Error, Socket};
+use std::ffi::CString;
+
+macro_rules! lock {
+    (shared) => {
+        let _lock = LOCK.read();
+    };
+    (exclusive) => {
+        let _lock = LOCK.write();
+    };
+}
+
+const STATS: &str = "
+     1234 transmitted frames (TXF)
+     5678 received frames (RXF)
+       90 matched frames (RXMF)
+
+        1 % total match ratio (RXMR)
+       12 frames/s total tx rate (TXR)
+       34 frames/s total rx rate (RXR)
+
+        2 % current match ratio (CRXMR)
+       56 frames/s current tx rate (CTXR)
+       78 frames/s current rx rate (CRXR)
+
+        3 % max match ratio (MRXMR)
+      910 frames/s max tx rate (MTXR)
+     1112 frames/s max rx rate (MRXR)
+
+        4 current receive list entries (CRCV)
+        5 maximum receive list entries (MRCV)
+
+        6 statistic resets (STR)
+
+";
+
+const RCVLIST: &str = "
+receive list 'rx_fil':
+  (any: no entry)
+  device   can_id   can_mask  function  userdata   matches  ident
+   vcan0     123    000007ff  00000000a1b2c3d4  00000000e5f60718         7  raw
+   vcan0  12345678  1fffffff  0000000000000000  0000000000000000         0  bcm
+  (vcan1: no entry)
+
+";
+
+#[test]
+fn test_parse_stats() {
+    let expected = CanStats {
+        tx_frames: 1234,
+        rx_frames: 5678,
+        matches: 90,
+        rates: Some(CanRates {
+            total_rx_match_ratio: 1,
+            total_tx_rate: 12,
+            total_rx_rate: 34,
+            current_rx_match_ratio: 2,
+            current_tx_rate: 56,
+            current_rx_rate: 78,
+            max_rx_match_ratio: 3,
+            max_tx_rate: 910,
+            max_rx_rate: 1112,
+        }),
+        rcv_entries: 4,
+        rcv_entries_max: 5,
+        stats_reset: 6,
+        user_reset: 0,
+    };
+    assert_eq!(parse_stats(STATS), Some(expected));
+}
+
+#[test]
+fn test_parse_stats_without_timer() {
+    let stats = "
+        1 transmitted frames (TXF)
+        2 received frames (RXF)
+        3 matched frames (RXMF)
+
+        4 current receive list entries (CRCV)
+        5 maximum receive list entries (MRCV)
+";
+    let stats = parse_stats(stats).unwrap();
+    assert_eq!(stats.rates, None);
+    assert_eq!((stats.rcv_entries, stats.rcv_entries_max), (4, 5));
+
+    assert_eq!(parse_stats("not stats"), None);
+}
+
+#[test]
+fn test_parse_receive_list() {
+    assert_eq!(
+        parse_receive_list(RCVLIST),
+        [
+            ReceiveListEntry {
+                device: Some("vcan0".to_owned()),
+                can_id: 0x123,
+                can_mask: sys::CAN_SFF_MASK,
+                function: 0xa1b2c3d4,
+                userdata: 0xe5f60718,
+                matches: 7,
+                ident: "raw".to_owned(),
+            },
+            ReceiveListEntry {
+                device: Some("vcan0".to_owned()),
+                can_id: 0x12345678,
+                can_mask: sys::CAN_EFF_MASK,
+                function: 0,
+                userdata: 0,
+                matches: 0,
+                ident: "bcm".to_owned(),
+            },
+        ]
+    );
+    assert!(parse_receive_list("\nreceive list 'rx_err':\n  (any: no entry)\n\n").is_empty());
+}
+
+#[test]
+fn test_link_stats() {
+    let stats = link_stats(CString::new("lo").unwrap()).unwrap();
+    assert!(stats.rx_packets >= stats.rx_dropped);
+    assert!(matches!(
+        link_stats(CString::new("NO DEVICE").unwrap()),
+        Err(Error::InterfaceNotFound { .. })
+    ));
+}
+
+#[test]
+#[ignore]
+fn test_can_stats() {
+    let stats = can_stats().unwrap();
+    assert!(stats.rcv_entries <= stats.rcv_entries_max);
+}
+
+#[test]
+#[ignore]
+fn test_receive_list() {
+    lock!(shared);
+    let _socket = Socket::bind(ifname()).unwrap();
+    let name = ifname().into_string().unwrap();
+    // the default filter of CAN_RAW matches all frames
+    assert!(receive_list(ReceiveList::All)
+        .unwrap()
+        .iter()
+        .any(|entry| entry.device.as_ref() == Some(&name) && entry.ident == "raw"));
+}
@@ -3,9 +3,22 @@
 #![allow(dead_code)]
 #![allow(non_camel_case_types)]
 #![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
 
 include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
 
+// macros using sizeof/offsetof are not translated by bindgen
+pub const CAN_MTU: usize = std::mem::size_of::<can_frame>();
+pub const CANFD_MTU: usize = std::mem::size_of::<canfd_frame>();
+// offsetof(struct canxl_frame, data) + CANFD_MAX_DLEN
+pub const CANXL_MIN_MTU: usize = 12 + CANFD_MAX_DLEN as usize;
+// sizeof(struct canxl_frame)
+pub const CANXL_MTU: usize = 12 + CANXL_MAX_DLEN as usize;
+
+// enum in linux/errqueue.h, which cannot be included without the libc headers
+pub const SCM_TSTAMP_SCHED: u32 = 1;
+pub const SCM_TSTAMP_ACK: u32 = 2;
+
 #[cfg(feature = "can-dlc-unaliased")]
 impl can_frame {
     pub(crate) fn len(&self) -> u8 {
@@ -0,0 +1,154 @@
+use crate::netlink::{bytes_of, MessageBuilder, Netlink};
+use crate::socket::if_nametoindex;
+use crate::{sys, MtuClass, Result};
+use std::ffi::{CStr, CString};
+use std::mem::MaybeUninit;
+use std::os::unix::io::RawFd;
+
+/// A virtual CAN interface created via rtnetlink, which is deleted on drop.
+/// Creating and deleting interfaces requires `CAP_NET_ADMIN`.
+///
+/// The interface is created down. Bring it up with [`CanLink::set_up`](crate::CanLink::set_up).
+///
+/// ```no_run
+/// use socketcan_alt::{CanLink, MtuClass, Socket, VirtualInterface};
+/// use std::ffi::CString;
+///
+/// let vcan = VirtualInterface::vcan(CString::new("vcan42")?, MtuClass::Fd)?;
+/// CanLink::new(vcan.name())?.set_up(true)?;
+/// let socket = Socket::bind(vcan.name())?;
+/// socket.set_fd_frames(true)?;
+///
+/// # std::io::Result::Ok(())
+/// ```
+#[derive(Debug)]
+pub struct VirtualInterface {
+    ifindex: u32,
+    name: CString,
+}
+
+impl VirtualInterface {
+    /// Creates a `vcan` interface, like `ip link add vcan0 type vcan`.
+    pub fn vcan<I>(name: I, mtu: MtuClass) -> Result<Self>
+    where
+        I: AsRef<CStr>,
+    {
+        let mut message = newlink(name.as_ref(), mtu);
+        message.nest(sys::IFLA_LINKINFO, |message| {
+            message.attr_string(sys::IFLA_INFO_KIND, "vcan");
+        });
+        Netlink::route()?.request(message)?;
+        Self::new(name)
+    }
+
+    /// Creates a pair of `vxcan` interfaces, which forward frames sent on one end to the other.
+    /// The peer is moved into the network namespace of `peer_netns`
+    /// (a file descriptor of `/proc/<pid>/ns/net`) if given.
+    ///
+    /// Deleting the interface deletes the peer as well.
+    pub fn vxcan<I, P>(name: I, peer: P, mtu: MtuClass, peer_netns: Option<RawFd>) -> Result<Self>
+    where
+        I: AsRef<CStr>,
+        P: AsRef<CStr>,
+    {
+        let mut message = newlink(name.as_ref(), mtu);
+        message.nest(sys::IFLA_LINKINFO, |message| {
+            message.attr_string(sys::IFLA_INFO_KIND, "vxcan").nest(
+                sys::IFLA_INFO_DATA,
+                |message| {
+                    // the peer is described as a nested RTM_NEWLINK message
+                    message.nest(sys::VXCAN_INFO_PEER, |message| {
+                        message
+                            .push(bytes_of(&ifinfomsg(0)))
+                            .attr(sys::IFLA_IFNAME, peer.as_ref().to_bytes_with_nul())
+                            .attr_value(sys::IFLA_MTU, &(mtu.mtu() as u32));
+                        if let Some(fd) = peer_netns {
+                            message.attr_value(sys::IFLA_NET_NS_FD, &(fd as u32));
+                        }
+                    });
+                },
+            );
+        });
+        Netlink::route()?.request(message)?;
+        Self::new(name)
+    }
+
+    fn new<I>(name: I) -> Result<Self>
+    where
+        I: AsRef<CStr>,
+    {
+        let ifindex = match if_nametoindex(name.as_ref()) {
+            Ok(ifindex) => ifindex,
+            Err(e) => {
+                // do not leak the interface just created
+                let _ = dellink_by_name(name.as_ref());
+                return Err(e);
+            }
+        };
+        Ok(Self {
+            ifindex,
+            name: name.as_ref().to_owned(),
+        })
+    }
+
+    pub fn ifindex(&self) -> u32 {
+        self.ifindex
+    }
+
+    pub fn name(&self) -> &CStr {
+        &self.name
+    }
+
+    /// Deletes the interface, reporting errors that are ignored on drop.
+    pub fn delete(mut self) -> Result<()> {
+        let ifindex = self.ifindex;
+        self.ifindex = 0;
+        dellink(ifindex)
+    }
+}
+
+impl Drop for VirtualInterface {
+    fn drop(&mut self) {
+        if self.ifindex != 0 {
+            let _ = dellink(self.ifindex);
+        }
+    }
+}
+
+fn ifinfomsg(ifindex: u32) -> sys::ifinfomsg {
+    let mut header = unsafe { MaybeUninit::<sys::ifinfomsg>::zeroed().assume_init() };
+    header.ifi_family = libc::AF_UNSPEC as _;
+    header.ifi_index = ifindex as _;
+    header
+}
+
+fn newlink(name: &CStr, mtu: MtuClass) -> MessageBuilder {
+    let mut message = MessageBuilder::new(
+        sys::RTM_NEWLINK,
+        sys::NLM_F_CREATE | sys::NLM_F_EXCL,
+        &ifinfomsg(0),
+    );
+    message
+        .attr(sys::IFLA_IFNAME, name.to_bytes_with_nul())
+        .attr_value(sys::IFLA_MTU, &(mtu.mtu() as u32));
+    message
+}
+
+fn dellink(ifindex: u32) -> Result<()> {
+    Netlink::route()?.request(MessageBuilder::new(
+        sys::RTM_DELLINK,
+        0,
+        &ifinfomsg(ifindex),
+    ))?;
+    Ok(())
+}
+
+fn dellink_by_name(name: &CStr) -> Result<()> {
+    let mut message = MessageBuilder::new(sys::RTM_DELLINK, 0, &ifinfomsg(0));
+    message.attr(sys::IFLA_IFNAME, name.to_bytes_with_nul());
+    Netlink::route()?.request(message)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,127 @@
+use super::{newlink, VirtualInterface};
+use crate::netlink::{read, read_string, Message};
+use crate::socket::if_nametoindex;
+use crate::socket::tests::{random_fd_data_standard, random_ifname};
+use crate::{
+    list_interfaces, sys, CanLink, DataFrame, Error, Frame, Id, LinkKind, MtuClass, Socket,
+};
+use std::ffi::CString;
+
+#[test]
+fn test_newlink() {
+    let name = CString::new("vcan42").unwrap();
+    let message = Message::parse(&newlink(&name, MtuClass::Xl).finish())
+        .pop()
+        .unwrap();
+    assert_eq!(message.ty(), sys::RTM_NEWLINK);
+    assert_eq!(
+        message.header.nlmsg_flags as u32,
+        sys::NLM_F_REQUEST | sys::NLM_F_CREATE | sys::NLM_F_EXCL
+    );
+    let mut attrs = message.attrs::<sys::ifinfomsg>();
+    let (ty, payload) = attrs.next().unwrap();
+    assert_eq!(ty, sys::IFLA_IFNAME);
+    assert_eq!(read_string(payload), "vcan42");
+    let (ty, payload) = attrs.next().unwrap();
+    assert_eq!(ty, sys::IFLA_MTU);
+    assert_eq!(read::<u32>(payload), Some(sys::CANXL_MTU as u32));
+}
+
+#[test]
+fn test_mtu_class() {
+    for &mtu in &[MtuClass::Classic, MtuClass::Fd, MtuClass::Xl] {
+        assert_eq!(MtuClass::from_mtu(mtu.mtu()), mtu);
+    }
+}
+
+#[test]
+#[ignore]
+fn test_vcan() {
+    let name = random_ifname("vcantest");
+    let vcan = VirtualInterface::vcan(&name, MtuClass::Fd).unwrap();
+    let interface = list_interfaces()
+        .unwrap()
+        .into_iter()
+        .find(|interface| interface.index == vcan.ifindex())
+        .unwrap();
+    assert_eq!(interface.kind, LinkKind::Vcan);
+    assert_eq!(interface.mtu_class, MtuClass::Fd);
+
+    CanLink::new(vcan.name()).unwrap().set_up(true).unwrap();
+    let socket = Socket::bind(vcan.name()).unwrap();
+    assert!(socket.supports_fd());
+
+    drop(vcan);
+    assert!(if_nametoindex(&name).is_err());
+}
+
+#[test]
+#[ignore]
+fn test_set_mtu_class() {
+    let name = random_ifname("vcantest");
+    let vcan = VirtualInterface::vcan(&name, MtuClass::Classic).unwrap();
+    let link = CanLink::new(vcan.name()).unwrap();
+    link.set_up(true).unwrap();
+    let socket = Socket::bind(vcan.name()).unwrap();
+    socket.set_fd_frames(true).unwrap();
+    assert!(matches!(
+        socket.send(&random_fd_data_standard()),
+        Err(Error::MtuMismatch { .. })
+    ));
+
+    link.set_up(false).unwrap();
+    link.set_mtu_class(MtuClass::Fd).unwrap();
+    link.set_up(true).unwrap();
+    assert_eq!(link.mtu_class().unwrap(), MtuClass::Fd);
+    assert_eq!(socket.mtu(), sys::CAN_MTU);
+    assert!(matches!(
+        socket.send(&random_fd_data_standard()),
+        Err(Error::MtuMismatch { .. })
+    ));
+    assert_eq!(socket.refresh_mtu().unwrap(), sys::CANFD_MTU);
+    socket.send(&random_fd_data_standard()).unwrap();
+
+    // the kernel rejects the frame and the MTU is re-validated
+    link.set_up(false).unwrap();
+    link.set_mtu_class(MtuClass::Classic).unwrap();
+    link.set_up(true).unwrap();
+    assert_eq!(socket.mtu(), sys::CANFD_MTU);
+    assert!(matches!(
+        socket.send(&random_fd_data_standard()),
+        Err(Error::MtuMismatch { .. })
+    ));
+    assert_eq!(socket.mtu(), sys::CAN_MTU);
+
+    link.set_up(false).unwrap();
+    link.set_mtu_class(MtuClass::Xl).unwrap();
+    assert_eq!(socket.refresh_mtu().unwrap(), sys::CANXL_MTU);
+    assert!(socket.supports_xl());
+}
+
+#[test]
+#[ignore]
+fn test_vcan_exists() {
+    let name = random_ifname("vcantest");
+    let vcan = VirtualInterface::vcan(&name, MtuClass::Classic).unwrap();
+    assert!(VirtualInterface::vcan(&name, MtuClass::Classic).is_err());
+    vcan.delete().unwrap();
+}
+
+#[test]
+#[ignore]
+fn test_vxcan() {
+    let name = random_ifname("vxcantest");
+    let peer = random_ifname("vxcantest");
+    let vxcan = VirtualInterface::vxcan(&name, &peer, MtuClass::Classic, None).unwrap();
+    CanLink::new(&name).unwrap().set_up(true).unwrap();
+    CanLink::new(&peer).unwrap().set_up(true).unwrap();
+
+    let socket = Socket::bind(&name).unwrap();
+    let peer_socket = Socket::bind(&peer).unwrap();
+    let frame = Frame::Data(DataFrame::new(Id::Standard(42), &[1, 2, 3]));
+    socket.send(&frame).unwrap();
+    assert_eq!(peer_socket.recv().unwrap(), frame);
+
+    vxcan.delete().unwrap();
+    assert!(if_nametoindex(&peer).is_err());
+}
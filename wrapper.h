@@ -1,2 +1,10 @@
 #include <linux/can.h>
 #include <linux/can/raw.h>
+#include <linux/can/bcm.h>
+#include <linux/can/isotp.h>
+#include <linux/can/j1939.h>
+#include <linux/if.h>
+#include <linux/rtnetlink.h>
+#include <linux/can/gw.h>
+#include <linux/can/netlink.h>
+#include <linux/can/vxcan.h>